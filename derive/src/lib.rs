@@ -0,0 +1,107 @@
+//! Derive macros for [`stable_identifier`](https://docs.rs/stable_identifier), enabled by its `derive` feature.
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, Member, Type, parse_macro_input, spanned::Spanned};
+
+/// Derives `IdentifyAs<D>` for a struct by returning a clone of one of its `Id<D>` fields.
+///
+/// The field is picked automatically if exactly one field has the type `Id<...>`. Otherwise, for
+/// example when using a type alias, it can be named explicitly with `#[identify(field = name)]`.
+#[proc_macro_derive(IdentifyAs, attributes(identify))]
+pub fn derive_identify_as(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand_identify_as(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_identify_as(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "IdentifyAs can only be derived for structs",
+        ));
+    };
+
+    let (member, ty) = match explicit_field(input)? {
+        Some(name) => find_named(&data.fields, &name)?,
+        None => find_id_field(&data.fields, input)?,
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let domain = quote!(<#ty as ::stable_identifier::__private::IdField>::Domain);
+
+    Ok(quote! {
+        impl #impl_generics ::stable_identifier::IdentifyAs<#domain> for #name #ty_generics #where_clause {
+            fn identify_as(&self) -> ::stable_identifier::Id<#domain> {
+                ::core::clone::Clone::clone(&self.#member)
+            }
+        }
+    })
+}
+
+fn explicit_field(input: &DeriveInput) -> syn::Result<Option<Ident>> {
+    let mut field = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("identify"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("field") {
+                field = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `field = <name>`"))
+            }
+        })?;
+    }
+    Ok(field)
+}
+
+fn find_named<'a>(fields: &'a Fields, name: &Ident) -> syn::Result<(Member, &'a Type)> {
+    fields
+        .iter()
+        .find(|field| field.ident.as_ref() == Some(name))
+        .map(|field| (Member::Named(name.clone()), &field.ty))
+        .ok_or_else(|| syn::Error::new(name.span(), format!("no field named `{name}`")))
+}
+
+fn find_id_field<'a>(fields: &'a Fields, input: &DeriveInput) -> syn::Result<(Member, &'a Type)> {
+    let mut candidates = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| is_id_type(&field.ty))
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(index.into()),
+            };
+            (member, &field.ty)
+        });
+
+    match (candidates.next(), candidates.next()) {
+        (Some(found), None) => Ok(found),
+        (None, _) => Err(syn::Error::new(
+            input.ident.span(),
+            "no field of type `Id<...>` found, name one with `#[identify(field = name)]`",
+        )),
+        (Some(_), Some(_)) => Err(syn::Error::new(
+            input.ident.span(),
+            "multiple fields of type `Id<...>` found, pick one with `#[identify(field = name)]`",
+        )),
+    }
+}
+
+fn is_id_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Id"),
+        _ => false,
+    }
+}
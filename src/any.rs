@@ -0,0 +1,132 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Id, IdDomain};
+
+/// An identifier with its domain erased, useful for storing identifiers of different domains together.
+///
+/// The domain is kept around by [`IdDomain::NAME`] and the backing is stored in its string form, so
+/// domain names should be unique for erased identifiers to be told apart. Two `AnyId` values are
+/// equal (and hash the same) only if both their domain and value are equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AnyId {
+    domain: &'static str,
+    value: String,
+}
+
+impl AnyId {
+    /// Constructs an erased identifier directly from a domain name and the string form of a backing value.
+    pub fn new(domain: &'static str, value: impl Into<String>) -> Self {
+        Self {
+            domain,
+            value: value.into(),
+        }
+    }
+
+    /// Name of the domain this identifier was erased from.
+    pub fn domain(&self) -> &'static str {
+        self.domain
+    }
+
+    /// String form of the backing value.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Checks whether this identifier was erased from domain `D`, by comparing domain names.
+    pub fn matches_domain<D: IdDomain>(&self) -> bool {
+        self.domain == D::NAME
+    }
+
+    /// Recovers a typed identifier, if this identifier belongs to domain `D` and its value can be parsed.
+    pub fn downcast<D: IdDomain>(&self) -> Option<Id<D>>
+    where
+        D::Backing: FromStr,
+    {
+        if !self.matches_domain::<D>() {
+            return None;
+        }
+        self.value.parse().ok().map(Id::new)
+    }
+}
+
+impl Display for AnyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} [{}]", self.domain, self.value)
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: Display,
+{
+    /// Erases the domain of this identifier, see [`AnyId`].
+    pub fn erase(&self) -> AnyId {
+        AnyId::new(D::NAME, self.backing().to_string())
+    }
+
+    /// The domain name and the displayed backing as a plain pair, e.g. for structured logging fields.
+    pub fn as_tagged(&self) -> (&'static str, String) {
+        (D::NAME, self.backing().to_string())
+    }
+}
+
+impl<D: IdDomain> From<Id<D>> for AnyId
+where
+    D::Backing: Display,
+{
+    fn from(id: Id<D>) -> Self {
+        id.erase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    struct Dog;
+    impl IdDomain for Dog {
+        const NAME: &'static str = "Dog";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    struct Cat;
+    impl IdDomain for Cat {
+        const NAME: &'static str = "Cat";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn erased_lookup() {
+        let mut ids = HashSet::new();
+        ids.insert(Dog::new_id("hans").erase());
+
+        assert!(ids.contains(&Dog::new_id("hans").erase()));
+        assert!(ids.contains(&AnyId::new("Dog", "hans")));
+        assert!(!ids.contains(&Cat::new_id("hans").erase()));
+    }
+
+    #[test]
+    fn tagged_pair() {
+        assert_eq!(Dog::new_id("hans").as_tagged(), ("Dog", "hans".to_string()));
+    }
+
+    #[test]
+    fn matches_domain() {
+        let erased = Dog::new_id("hans").erase();
+        assert!(erased.matches_domain::<Dog>());
+        assert!(!erased.matches_domain::<Cat>());
+    }
+
+    #[test]
+    fn downcast() {
+        let erased = AnyId::from(Dog::new_id("hans"));
+        assert_eq!(erased.downcast::<Dog>(), Some(Dog::new_id("hans")));
+        assert_eq!(erased.downcast::<Cat>(), None);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{Id, IdDomain};
+
+/// Conversion between identifiers of domain `D` and an identifier type from another library.
+///
+/// Implementing this once for a foreign type lets you convert both ways using
+/// [`Id::from_foreign`] and [`Id::into_foreign`]:
+/// ```
+/// use stable_identifier::*;
+///
+/// // Some other library's identifier type.
+/// struct LibUserId(u64);
+///
+/// struct User;
+/// impl IdDomain for User {
+///     const NAME: &'static str = "User";
+///     type Backing = u64;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// impl IdBridge<User> for LibUserId {
+///     fn into_backing(self) -> u64 {
+///         self.0
+///     }
+///
+///     fn from_backing(backing: u64) -> Self {
+///         LibUserId(backing)
+///     }
+/// }
+///
+/// // Lets `.into()` convert as well, see below.
+/// impl From<LibUserId> for Id<User> {
+///     fn from(lib_id: LibUserId) -> Self {
+///         lib_id.into_id()
+///     }
+/// }
+///
+/// let id: Id<User> = LibUserId(7).into_id();
+/// let lib_id: LibUserId = id.into_foreign();
+/// assert_eq!(lib_id.0, 7);
+///
+/// let id: Id<User> = LibUserId(8).into();
+/// assert_eq!(*id.backing(), 8);
+/// ```
+///
+/// This crate can't provide `From<T> for Id<D>` for every bridged `T`: such a blanket implementation
+/// would overlap with the standard `impl<T> From<T> for T`, since `Id<D>` itself could implement
+/// `IdBridge<D>`, which the compiler rejects. To convert with `.into()`, write the `From` implementation
+/// for your foreign type yourself as shown above. It delegates to [`IdBridge::into_id`], so there is still
+/// only one conversion to maintain.
+///
+/// Identifier types like [`Uuid`](https://docs.rs/uuid) or [`Ulid`](https://docs.rs/ulid) don't need a bridge,
+/// they can be used directly as [`IdDomain::Backing`].
+pub trait IdBridge<D: IdDomain>: Sized {
+    /// Converts the foreign identifier into a backing value of domain `D`.
+    fn into_backing(self) -> D::Backing;
+
+    /// Converts a backing value of domain `D` into the foreign identifier.
+    fn from_backing(backing: D::Backing) -> Self;
+
+    /// Converts the foreign identifier into an identifier of domain `D`.
+    fn into_id(self) -> Id<D> {
+        Id::new(self.into_backing())
+    }
+}
+
+impl<D: IdDomain> Id<D> {
+    /// Constructs an identifier from a foreign identifier type, see [`IdBridge`].
+    pub fn from_foreign<T: IdBridge<D>>(foreign: T) -> Self {
+        foreign.into_id()
+    }
+
+    /// Converts this identifier into a foreign identifier type, see [`IdBridge`].
+    pub fn into_foreign<T: IdBridge<D>>(self) -> T {
+        T::from_backing(self.into_backing())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct ForeignId {
+        raw: String,
+    }
+
+    struct Account;
+    impl IdDomain for Account {
+        const NAME: &'static str = "Account";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    impl IdBridge<Account> for ForeignId {
+        fn into_backing(self) -> String {
+            self.raw
+        }
+
+        fn from_backing(backing: String) -> Self {
+            ForeignId { raw: backing }
+        }
+    }
+
+    #[test]
+    fn bridge_both_ways() {
+        let foreign = ForeignId {
+            raw: "acc-1".to_string(),
+        };
+        let id = Id::<Account>::from_foreign(foreign);
+        assert_eq!(id, Account::new_id("acc-1"));
+
+        let back: ForeignId = id.into_foreign();
+        assert_eq!(
+            back,
+            ForeignId {
+                raw: "acc-1".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::{HashMap, HashSet, hash_set},
+    hash::Hash,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{Id, IdDomain, IdentifyAs};
+
+/// A [`HashMap`] keyed by identifiers of domain `D`.
+///
+/// Dereferences to the inner `HashMap`, and adds conveniences for values that can
+/// identify themselves through [`IdentifyAs`].
+pub struct IdMap<D: IdDomain, V> {
+    map: HashMap<Id<D>, V>,
+}
+
+impl<D: IdDomain, V> IdMap<D, V>
+where
+    D::Backing: Eq + Hash,
+{
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+
+    /// Returns the inner `HashMap`.
+    pub fn into_inner(self) -> HashMap<Id<D>, V> {
+        self.map
+    }
+}
+
+impl<D: IdDomain, V> IdMap<D, V>
+where
+    D::Backing: Eq + Hash,
+    V: IdentifyAs<D>,
+{
+    /// Builds a map from identifiable items, keying each item by its own identifier.
+    ///
+    /// If several items share an identifier, the last one wins.
+    pub fn from_identifiable(items: impl IntoIterator<Item = V>) -> Self {
+        items
+            .into_iter()
+            .map(|item| (item.identify_as(), item))
+            .collect()
+    }
+
+    /// Adds identifiable items, keying each item by its own identifier.
+    ///
+    /// Items replace existing values stored under the same identifier.
+    pub fn extend_identifiable(&mut self, items: impl IntoIterator<Item = V>) {
+        self.extend(items.into_iter().map(|item| (item.identify_as(), item)));
+    }
+
+    /// Looks up the value stored under the identifier of `item`.
+    pub fn get_for(&self, item: &impl IdentifyAs<D>) -> Option<&V> {
+        self.map.get(&item.identify_as())
+    }
+}
+
+impl<D: IdDomain, V> Default for IdMap<D, V>
+where
+    D::Backing: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: IdDomain, V> Clone for IdMap<D, V>
+where
+    D::Backing: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<D: IdDomain, V> std::fmt::Debug for IdMap<D, V>
+where
+    D::Backing: std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.map.fmt(f)
+    }
+}
+
+impl<D: IdDomain, V> Deref for IdMap<D, V> {
+    type Target = HashMap<Id<D>, V>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<D: IdDomain, V> DerefMut for IdMap<D, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.map
+    }
+}
+
+impl<D: IdDomain, V> From<HashMap<Id<D>, V>> for IdMap<D, V> {
+    fn from(map: HashMap<Id<D>, V>) -> Self {
+        Self { map }
+    }
+}
+
+impl<D: IdDomain, V> FromIterator<(Id<D>, V)> for IdMap<D, V>
+where
+    D::Backing: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = (Id<D>, V)>>(iter: I) -> Self {
+        Self {
+            map: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<D: IdDomain, V> Extend<(Id<D>, V)> for IdMap<D, V>
+where
+    D::Backing: Eq + Hash,
+{
+    fn extend<I: IntoIterator<Item = (Id<D>, V)>>(&mut self, iter: I) {
+        self.map.extend(iter);
+    }
+}
+
+/// Re-keys a map from domain `Old` to domain `New`, e.g. when splitting or renaming a domain.
+///
+/// Each key is passed through `f`. If several old keys map to the same new key, only one of their
+/// values is kept, and which one is unspecified.
+pub fn remap_keys<Old, New, V, S, F>(map: HashMap<Id<Old>, V, S>, f: F) -> HashMap<Id<New>, V>
+where
+    Old: IdDomain,
+    New: IdDomain,
+    New::Backing: Eq + Hash,
+    F: Fn(Id<Old>) -> Id<New>,
+{
+    map.into_iter().map(|(id, value)| (f(id), value)).collect()
+}
+
+/// A [`HashSet`] of identifiers of domain `D`.
+///
+/// Dereferences to the inner `HashSet`, and adds conveniences for items that can
+/// identify themselves through [`IdentifyAs`].
+pub struct IdSet<D: IdDomain> {
+    set: HashSet<Id<D>>,
+}
+
+impl<D: IdDomain> IdSet<D>
+where
+    D::Backing: Eq + Hash,
+{
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self {
+            set: HashSet::new(),
+        }
+    }
+
+    /// Returns the inner `HashSet`.
+    pub fn into_inner(self) -> HashSet<Id<D>> {
+        self.set
+    }
+
+    /// Builds a set from the identifiers of identifiable items.
+    pub fn from_identifiable<T: IdentifyAs<D>>(items: impl IntoIterator<Item = T>) -> Self {
+        items.into_iter().map(|item| item.identify_as()).collect()
+    }
+
+    /// Adds the identifiers of identifiable items.
+    pub fn extend_identifiable<T: IdentifyAs<D>>(&mut self, items: impl IntoIterator<Item = T>) {
+        self.extend(items.into_iter().map(|item| item.identify_as()));
+    }
+
+    /// Checks whether the identifier of `item` is in the set.
+    pub fn contains_item(&self, item: &impl IdentifyAs<D>) -> bool {
+        self.set.contains(&item.identify_as())
+    }
+
+    /// Removes all identifiers from the set, yielding them as owned values.
+    ///
+    /// The set is empty afterwards, but keeps its allocated memory for reuse.
+    pub fn drain(&mut self) -> hash_set::Drain<'_, Id<D>> {
+        self.set.drain()
+    }
+}
+
+impl<D: IdDomain> Default for IdSet<D>
+where
+    D::Backing: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: IdDomain> Clone for IdSet<D>
+where
+    D::Backing: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            set: self.set.clone(),
+        }
+    }
+}
+
+impl<D: IdDomain> std::fmt::Debug for IdSet<D>
+where
+    D::Backing: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.set.fmt(f)
+    }
+}
+
+impl<D: IdDomain> Deref for IdSet<D> {
+    type Target = HashSet<Id<D>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.set
+    }
+}
+
+impl<D: IdDomain> DerefMut for IdSet<D> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.set
+    }
+}
+
+impl<D: IdDomain> From<HashSet<Id<D>>> for IdSet<D> {
+    fn from(set: HashSet<Id<D>>) -> Self {
+        Self { set }
+    }
+}
+
+impl<D: IdDomain> FromIterator<Id<D>> for IdSet<D>
+where
+    D::Backing: Eq + Hash,
+{
+    fn from_iter<I: IntoIterator<Item = Id<D>>>(iter: I) -> Self {
+        Self {
+            set: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<D: IdDomain> Extend<Id<D>> for IdSet<D>
+where
+    D::Backing: Eq + Hash,
+{
+    fn extend<I: IntoIterator<Item = Id<D>>>(&mut self, iter: I) {
+        self.set.extend(iter);
+    }
+}
+
+impl<D: IdDomain> IntoIterator for IdSet<D> {
+    type Item = Id<D>;
+    type IntoIter = hash_set::IntoIter<Id<D>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.set.into_iter()
+    }
+}
+
+impl<'a, D: IdDomain> IntoIterator for &'a IdSet<D> {
+    type Item = &'a Id<D>;
+    type IntoIter = hash_set::Iter<'a, Id<D>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.set.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dog;
+    impl IdDomain for Dog {
+        const NAME: &'static str = "Dog";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct DogRecord {
+        id: Id<Dog>,
+        name: &'static str,
+    }
+
+    impl IdentifyAs<Dog> for DogRecord {
+        fn identify_as(&self) -> Id<Dog> {
+            self.id.clone()
+        }
+    }
+
+    #[test]
+    fn populate_from_identifiable() {
+        let dogs = vec![
+            DogRecord {
+                id: Dog::new_id("rex"),
+                name: "Rex",
+            },
+            DogRecord {
+                id: Dog::new_id("fido"),
+                name: "Fido",
+            },
+        ];
+        let map = IdMap::from_identifiable(dogs);
+        assert_eq!(map.len(), 2);
+
+        let lookup = DogRecord {
+            id: Dog::new_id("fido"),
+            name: "Someone else",
+        };
+        assert_eq!(map.get_for(&lookup).map(|dog| dog.name), Some("Fido"));
+        assert_eq!(
+            map.get(&Dog::new_id("rex")).map(|dog| dog.name),
+            Some("Rex")
+        );
+    }
+
+    #[test]
+    fn set_yields_owned_ids() {
+        let mut set: IdSet<Dog> = ["rex", "fido"].map(Dog::new_id).into_iter().collect();
+        assert!(set.contains_item(&DogRecord {
+            id: Dog::new_id("rex"),
+            name: "Rex",
+        }));
+
+        let mut drained: Vec<Id<Dog>> = set.drain().collect();
+        drained.sort();
+        assert_eq!(drained, [Dog::new_id("fido"), Dog::new_id("rex")]);
+        assert!(set.is_empty());
+
+        set.insert(Dog::new_id("spot"));
+        let owned: Vec<Id<Dog>> = set.into_iter().collect();
+        assert_eq!(owned, [Dog::new_id("spot")]);
+    }
+
+    #[test]
+    fn remap_keys_between_domains() {
+        struct Puppy;
+        impl IdDomain for Puppy {
+            const NAME: &'static str = "Puppy";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let ages = HashMap::from([(Dog::new_id("rex"), 1), (Dog::new_id("fido"), 2)]);
+        let remapped: HashMap<Id<Puppy>, i32> =
+            remap_keys(ages, |id| Puppy::new_id(format!("puppy-{}", id.backing())));
+
+        assert_eq!(remapped.len(), 2);
+        assert_eq!(remapped[&Puppy::new_id("puppy-rex")], 1);
+        assert_eq!(remapped[&Puppy::new_id("puppy-fido")], 2);
+    }
+
+    #[test]
+    fn extend_from_identifiable() {
+        let records = [
+            DogRecord {
+                id: Dog::new_id("rex"),
+                name: "Rex",
+            },
+            DogRecord {
+                id: Dog::new_id("fido"),
+                name: "Fido",
+            },
+        ];
+
+        let mut set = IdSet::new();
+        set.extend(records.iter().map(|dog| dog.identify_as()));
+        set.extend_identifiable([DogRecord {
+            id: Dog::new_id("spot"),
+            name: "Spot",
+        }]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Dog::new_id("spot")));
+        assert!(set.contains_item(&records[1]));
+
+        let mut map = IdMap::new();
+        map.extend_identifiable(records);
+        map.extend([(
+            Dog::new_id("rex"),
+            DogRecord {
+                id: Dog::new_id("rex"),
+                name: "Rex II",
+            },
+        )]);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&Dog::new_id("rex")].name, "Rex II");
+    }
+}
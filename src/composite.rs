@@ -0,0 +1,146 @@
+use crate::{ConstPath, Id, IdDomain};
+
+/// A backing that packs the backings of two other identifiers, e.g. a seat identified by its room
+/// and its seat number, see [`Id::from_parts`].
+///
+/// Implemented for:
+/// - Unsigned integers, packing two integers of half the size by putting the first in the high bits.
+///   This preserves both equality and ordering of the `(first, second)` pairs. Signed integers are not
+///   supported, since packing negative parts would break the ordering.
+/// - `String`, joining two strings with [`ConstPath::SEPARATOR`]. The first part must not contain the
+///   separator, so equality is preserved. Ordering is not preserved when one first part is a prefix of
+///   another that continues with a character sorting before the separator `:`, i.e. a control character,
+///   a space, a digit or one of ``!"#$%&'()*+,-./``. For example, `("a1", ..)` packs to something smaller
+///   than `("a", ..)`.
+pub trait CompositeBacking<A, B> {
+    /// Packs two parts into one backing.
+    fn pack(first: A, second: B) -> Self;
+
+    /// Unpacks the two parts again.
+    fn unpack(&self) -> (A, B);
+}
+
+macro_rules! impl_composite_backing {
+    ($($ty:ty => $half:ty),*) => {
+        $(
+            impl CompositeBacking<$half, $half> for $ty {
+                fn pack(first: $half, second: $half) -> Self {
+                    (<$ty>::from(first) << <$half>::BITS) | <$ty>::from(second)
+                }
+
+                fn unpack(&self) -> ($half, $half) {
+                    ((self >> <$half>::BITS) as $half, *self as $half)
+                }
+            }
+        )*
+    };
+}
+
+impl_composite_backing!(u16 => u8, u32 => u16, u64 => u32, u128 => u64);
+
+impl CompositeBacking<String, String> for String {
+    /// Panics if the first part contains [`ConstPath::SEPARATOR`], since the packed string could then
+    /// not be told apart from other pairs of parts.
+    fn pack(first: String, second: String) -> Self {
+        let separator = ConstPath::<2>::SEPARATOR;
+        assert!(
+            !first.contains(separator),
+            "first part of a composite identifier must not contain {separator:?}"
+        );
+        format!("{first}{separator}{second}")
+    }
+
+    /// Without a separator, the whole string is taken as the first part.
+    fn unpack(&self) -> (String, String) {
+        match self.split_once(ConstPath::<2>::SEPARATOR) {
+            Some((first, second)) => (first.to_owned(), second.to_owned()),
+            None => (self.clone(), String::new()),
+        }
+    }
+}
+
+impl<D: IdDomain> Id<D> {
+    /// Builds a composite identifier out of the identifiers of two other domains.
+    ///
+    /// Panics for `String` backings if the first part contains [`ConstPath::SEPARATOR`], see [`CompositeBacking`].
+    ///
+    /// ```
+    /// use stable_identifier::*;
+    ///
+    /// define_id!(Room, "Room", backing = u32 => RoomId);
+    /// define_id!(SeatNumber, "SeatNumber", backing = u32 => SeatNumberId);
+    /// define_id!(Seat, "Seat", backing = u64 => SeatId);
+    ///
+    /// let seat = SeatId::from_parts(Room::new_id(3u32), SeatNumber::new_id(12u32));
+    /// let (room, number): (RoomId, SeatNumberId) = seat.parts();
+    /// assert_eq!(room, Room::new_id(3u32));
+    /// assert_eq!(number, SeatNumber::new_id(12u32));
+    /// ```
+    pub fn from_parts<A, B>(first: Id<A>, second: Id<B>) -> Self
+    where
+        A: IdDomain,
+        B: IdDomain,
+        D::Backing: CompositeBacking<A::Backing, B::Backing>,
+    {
+        Id::new(D::Backing::pack(
+            first.into_backing(),
+            second.into_backing(),
+        ))
+    }
+
+    /// Splits a composite identifier into the identifiers it was built from, see [`Id::from_parts`].
+    pub fn parts<A, B>(&self) -> (Id<A>, Id<B>)
+    where
+        A: IdDomain,
+        B: IdDomain,
+        D::Backing: CompositeBacking<A::Backing, B::Backing>,
+    {
+        let (first, second) = self.backing().unpack();
+        (Id::new(first), Id::new(second))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::define_id!(Room, "Room", backing = u32 => RoomId);
+    crate::define_id!(SeatNumber, "SeatNumber", backing = u32 => SeatNumberId);
+    crate::define_id!(Seat, "Seat", backing = u64 => SeatId);
+
+    #[test]
+    fn pack_and_unpack_integers() {
+        let seat = SeatId::from_parts(Room::new_id(3u32), SeatNumber::new_id(12u32));
+        assert_eq!(*seat.backing(), (3 << 32) | 12);
+
+        let (room, number): (RoomId, SeatNumberId) = seat.parts();
+        assert_eq!(room, Room::new_id(3u32));
+        assert_eq!(number, SeatNumber::new_id(12u32));
+
+        let next_room = SeatId::from_parts(Room::new_id(4u32), SeatNumber::new_id(0u32));
+        let same_room = SeatId::from_parts(Room::new_id(3u32), SeatNumber::new_id(u32::MAX));
+        assert!(seat < same_room);
+        assert!(same_room < next_room);
+    }
+
+    #[test]
+    fn pack_and_unpack_strings() {
+        crate::define_id!(Team, "Team", backing = String => TeamId);
+        crate::define_id!(User, "User", backing = String => UserId);
+        crate::define_id!(Member, "Member", backing = String => MemberId);
+
+        let member = MemberId::from_parts(Team::new_id("core"), User::new_id("anna:b"));
+        assert_eq!(member.backing(), "core:anna:b");
+
+        let (team, user): (TeamId, UserId) = member.parts();
+        assert_eq!(team, Team::new_id("core"));
+        assert_eq!(user, User::new_id("anna:b"));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not contain")]
+    fn separator_in_first_string_part() {
+        // Would otherwise pack to the same string as ("a", "b:c").
+        String::pack("a:b".to_owned(), "c".to_owned());
+    }
+}
@@ -0,0 +1,119 @@
+//! Content-addressed identifiers, derived by hashing content with [BLAKE3](https://docs.rs/blake3).
+use blake3::OutputReader;
+
+use crate::{GenerateIdFromContent, Id, IdDomain};
+
+/// Backing types that can be filled from the output of a content hash.
+pub trait FromContentHash {
+    /// Builds a backing value from the (extendable) output of a BLAKE3 hash.
+    fn from_content_hash(output: &mut OutputReader) -> Self;
+}
+
+impl<const N: usize> FromContentHash for [u8; N] {
+    fn from_content_hash(output: &mut OutputReader) -> Self {
+        let mut bytes = [0u8; N];
+        output.fill(&mut bytes);
+        bytes
+    }
+}
+
+/// Fills the ID with characters of the [`UrlSafe`](crate::tiny_id::UrlSafe) alphabet.
+#[cfg(feature = "tiny_id")]
+impl<const N: usize> FromContentHash for crate::tiny_id::TinyId<N> {
+    fn from_content_hash(output: &mut OutputReader) -> Self {
+        use crate::tiny_id::{Alphabet, UrlSafe};
+
+        let bytes = <[u8; N]>::from_content_hash(output);
+        // The url-safe alphabet has exactly 64 characters, so every 6 bits pick one without bias.
+        let text: String = bytes
+            .iter()
+            .map(|byte| UrlSafe::CHARS[usize::from(byte & 63)])
+            .collect();
+        Self::from_bytes(text.as_bytes())
+    }
+}
+
+#[cfg(feature = "tiny_id")]
+impl<const N: usize> FromContentHash for crate::tiny_id::BinId<N> {
+    fn from_content_hash(output: &mut OutputReader) -> Self {
+        Self::new(<[u8; N]>::from_content_hash(output))
+    }
+}
+
+/// Produces a version 8 (custom) UUID from the first 16 bytes of the hash.
+#[cfg(feature = "uuid")]
+impl FromContentHash for uuid::Uuid {
+    fn from_content_hash(output: &mut OutputReader) -> Self {
+        uuid::Builder::from_custom_bytes(<[u8; 16]>::from_content_hash(output)).into_uuid()
+    }
+}
+
+/// Derives identifiers from content by hashing it with BLAKE3, for any backing implementing [`FromContentHash`].
+///
+/// The same content always produces the same identifier, while different content produces different
+/// identifiers (unless the backing is too small to avoid collisions, so mind its length).
+/// ```
+/// use stable_identifier::{*, content::ContentAddressedGen};
+///
+/// struct Blob;
+/// impl IdDomain for Blob {
+///     const NAME: &'static str = "Blob";
+///     type Backing = [u8; 16];
+///     type Generator = ContentAddressedGen;
+///     type ConstRepr = ();
+/// }
+///
+/// assert_eq!(Blob::id_from_content(b"hello"), Blob::id_from_content(b"hello"));
+/// ```
+pub struct ContentAddressedGen;
+
+impl<D> GenerateIdFromContent<D> for ContentAddressedGen
+where
+    D: IdDomain,
+    D::Backing: FromContentHash,
+{
+    fn id_from_content(content: &[u8]) -> Id<D> {
+        let mut output = blake3::Hasher::new().update(content).finalize_xof();
+        Id::new(D::Backing::from_content_hash(&mut output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Blob;
+    impl IdDomain for Blob {
+        const NAME: &'static str = "Blob";
+        type Backing = [u8; 16];
+        type Generator = ContentAddressedGen;
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn identical_content_identical_id() {
+        let first = Blob::id_from_content(b"some content");
+        let second = Blob::id_from_content("some content");
+        assert_eq!(first, second);
+        assert_ne!(first, Blob::id_from_content(b"other content"));
+    }
+
+    #[cfg(feature = "tiny_id")]
+    #[test]
+    fn tiny_id_content() {
+        use crate::tiny_id::TinyId;
+
+        struct Page;
+        impl IdDomain for Page {
+            const NAME: &'static str = "Page";
+            type Backing = TinyId<12>;
+            type Generator = ContentAddressedGen;
+            type ConstRepr = ();
+        }
+
+        let id = Page::id_from_content("<h1>hi</h1>");
+        assert_eq!(id.backing().len(), 12);
+        assert_eq!(id, Page::id_from_content("<h1>hi</h1>"));
+        assert_ne!(id, Page::id_from_content("<h1>hello</h1>"));
+    }
+}
@@ -0,0 +1,243 @@
+//! [Crockford base32](https://www.crockford.com/base32.html) encoding, for compact identifiers that
+//! are easy to read out loud and sort the same way as the bytes they encode.
+//!
+//! The alphabet is [`ALPHABET`]: the digits and uppercase letters, without `I`, `L`, `O` and `U`,
+//! which are easily confused with `1`, `0` and `V` or make accidental words. Encoding always produces
+//! uppercase. Decoding also accepts lowercase, but rejects `I`, `L`, `O` and `U` instead of
+//! guessing what was meant, so that every identifier has exactly one valid encoding.
+//!
+//! Bytes are encoded as one big-endian number, padded with zero bits at the front, like
+//! [`Ulid`](https://docs.rs/ulid) does. Encodings of inputs of the same length therefore sort the
+//! same way as the inputs.
+use std::fmt::Display;
+
+use crate::{Id, IdDomain, IdError, ProtoBacked};
+
+/// The 32 characters used for encoding, in order of their value.
+pub const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Encodes bytes as Crockford base32, using `ceil(8 * len / 5)` characters.
+///
+/// ```
+/// use stable_identifier::crockford;
+///
+/// assert_eq!(crockford::encode(&[0xff, 0x00]), "1ZR0");
+/// assert_eq!(crockford::decode("1ZR0").unwrap(), [0xff, 0x00]);
+/// ```
+pub fn encode(bytes: &[u8]) -> String {
+    let bits = bytes.len() * 8;
+    let chars = bits.div_ceil(5);
+    let mut out = String::with_capacity(chars);
+    let mut buffer: u32 = 0;
+    // Start with the padding bits, so that the last character ends exactly on the last byte.
+    let mut buffered = chars * 5 - bits;
+    for &byte in bytes {
+        buffer = (buffer << 8) | u32::from(byte);
+        buffered += 8;
+        while buffered >= 5 {
+            buffered -= 5;
+            out.push(ALPHABET[((buffer >> buffered) & 31) as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decodes Crockford base32 made by [`encode`] back into bytes.
+///
+/// Fails on characters outside the alphabet, and on encodings that can't have been made by [`encode`].
+pub fn decode(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    let values = encoded
+        .char_indices()
+        .map(|(index, ch)| decode_char(ch).ok_or(DecodeError::InvalidChar { ch, index }))
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let len = values.len() * 5 / 8;
+    if (len * 8).div_ceil(5) != values.len() {
+        return Err(DecodeError::Length(values.len()));
+    }
+    let padding = values.len() * 5 - len * 8;
+
+    let mut out = Vec::with_capacity(len);
+    let mut buffer: u32 = 0;
+    let mut buffered = 0;
+    for (index, &value) in values.iter().enumerate() {
+        let value_bits = if index == 0 { 5 - padding } else { 5 };
+        if u32::from(value) >> value_bits != 0 {
+            return Err(DecodeError::Overflow);
+        }
+        buffer = (buffer << 5) | u32::from(value);
+        buffered += value_bits;
+        if buffered >= 8 {
+            buffered -= 8;
+            out.push((buffer >> buffered) as u8);
+        }
+    }
+    Ok(out)
+}
+
+fn decode_char(ch: char) -> Option<u8> {
+    if !ch.is_ascii() {
+        return None;
+    }
+    let upper = ch.to_ascii_uppercase() as u8;
+    ALPHABET
+        .iter()
+        .position(|&c| c == upper)
+        .map(|value| value as u8)
+}
+
+/// Reasons why a string is not valid Crockford base32, see [`decode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A character outside the alphabet, including the excluded `I`, `L`, `O` and `U`.
+    InvalidChar {
+        /// The offending character.
+        ch: char,
+        /// Its byte index in the input.
+        index: usize,
+    },
+    /// No number of bytes is encoded with this many characters.
+    Length(usize),
+    /// The leading padding bits were not zero, so the value doesn't fit in the bytes.
+    Overflow,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidChar { ch, index } => {
+                write!(f, "invalid Crockford base32 character {ch:?} at {index}")
+            }
+            DecodeError::Length(chars) => {
+                write!(
+                    f,
+                    "{chars} characters is not a valid Crockford base32 length"
+                )
+            }
+            DecodeError::Overflow => write!(f, "Crockford base32 value is too large"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: ProtoBacked,
+{
+    /// Encodes the bytes of the backing (see [`ProtoBacked`]) as Crockford base32, see the [module docs](self).
+    ///
+    /// For fixed-size backings like byte arrays and ULIDs, the encoding sorts like the backing does.
+    pub fn to_crockford(&self) -> String {
+        encode(&self.to_proto_bytes())
+    }
+
+    /// Decodes an identifier encoded with [`Id::to_crockford`].
+    pub fn from_crockford(encoded: &str) -> Result<Self, IdError> {
+        let bytes = decode(encoded).map_err(|err| IdError::parse::<D>(encoded, err))?;
+        Self::from_proto_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for bytes in [
+            &[][..],
+            &[0],
+            &[255],
+            &[1, 2, 3, 4, 5],
+            &[0xde, 0xad, 0xbe, 0xef, 0, 7],
+        ] {
+            let encoded = encode(bytes);
+            assert_eq!(encoded.len(), (bytes.len() * 8).div_ceil(5));
+            assert_eq!(decode(&encoded).unwrap(), bytes);
+            assert_eq!(decode(&encoded.to_lowercase()).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn preserves_order() {
+        let mut values: Vec<[u8; 3]> = (0..2000u32)
+            .map(|n| {
+                let n = n.wrapping_mul(2_654_435_761) >> 8;
+                [(n >> 16) as u8, (n >> 8) as u8, n as u8]
+            })
+            .collect();
+        values.sort();
+        let encoded: Vec<_> = values.iter().map(|bytes| encode(bytes)).collect();
+        assert!(encoded.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn rejects_ambiguous_characters() {
+        for ch in ['I', 'L', 'O', 'U', 'i', 'l', 'o', 'u'] {
+            let encoded = format!("0{ch}");
+            assert_eq!(
+                decode(&encoded),
+                Err(DecodeError::InvalidChar { ch, index: 1 })
+            );
+        }
+        assert_eq!(decode("000"), Err(DecodeError::Length(3)));
+        assert_eq!(decode("80"), Err(DecodeError::Overflow));
+        assert_eq!(decode("70"), Ok(vec![0xe0]));
+    }
+
+    #[test]
+    fn id_round_trip() {
+        struct Key;
+        impl IdDomain for Key {
+            const NAME: &'static str = "Key";
+            type Backing = [u8; 4];
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Key::new_id([1, 2, 3, 4]);
+        let encoded = id.to_crockford();
+        assert_eq!(Id::<Key>::from_crockford(&encoded), Ok(id));
+        assert_eq!(
+            Id::<Key>::from_crockford("0I0").unwrap_err().domain(),
+            "Key"
+        );
+    }
+
+    #[cfg(feature = "tiny_id")]
+    #[test]
+    fn rejects_malformed_tiny_id() {
+        use crate::tiny_id::TinyId;
+
+        struct Tag;
+        impl IdDomain for Tag {
+            const NAME: &'static str = "Tag";
+            type Backing = TinyId<5>;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Tag::new_id(TinyId::from("ab"));
+        assert_eq!(Id::<Tag>::from_crockford(&id.to_crockford()), Ok(id));
+        assert!(Id::<Tag>::from_crockford(&encode(b"a\0b\0\0")).is_err());
+        assert!(Id::<Tag>::from_crockford(&encode(&[b'a', 0, 0xff, 0, 0])).is_err());
+    }
+
+    #[cfg(feature = "ulid")]
+    #[test]
+    fn matches_ulid() {
+        struct Event;
+        impl IdDomain for Event {
+            const NAME: &'static str = "Event";
+            type Backing = ulid::Ulid;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let ulid = ulid::Ulid::new();
+        let id = Event::new_id(ulid);
+        assert_eq!(id.to_crockford(), ulid.to_string());
+        assert_eq!(Id::<Event>::from_crockford(&ulid.to_string()), Ok(id));
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{GenerateIdStateful, GenerateIdStateless, Id};
+use std::{borrow::Cow, fmt::Display, ops::Range, str::FromStr};
+
+use crate::{
+    GenerateBatch, GenerateError, GenerateIdFromContent, GenerateIdNamed, GenerateIdStateful,
+    GenerateIdStateless, GenerateIdWith, Id, IdError, RangeGen, TryGenerateIdStateful,
+};
 
 /// Defines a "domain" of identifiable entities.
 ///
@@ -11,6 +16,13 @@ pub trait IdDomain {
     /// A presentable name for the ID domain, useful in debugging.
     const NAME: &'static str;
 
+    /// A human readable description of what this domain identifies, for documentation generators
+    /// and admin interfaces. Empty by default.
+    const DESCRIPTION: &'static str = "";
+
+    /// A category to group related domains by in tooling, e.g. `"billing"`. Empty by default.
+    const CATEGORY: &'static str = "";
+
     /// The type to use as a concrete data structure for identifiers in this domain.
     ///
     /// `Id<Self>` will automatically implement the following traits if they are implemented by this inner backing type:
@@ -29,11 +41,21 @@ pub trait IdDomain {
     ///
     /// You can of course just use [`String`], although it lacks the convenience of
     /// stack-allocated, [`Copy`]-able ID types.
+    ///
+    /// Signed integers work as backings too, displaying and ordering negative identifiers the same way
+    /// as the integers themselves. A few helpers only accept unsigned integers though, because they count up
+    /// from zero or pack bits: [`crate::CounterGen`] and [`crate::CompositeBacking`].
     type Backing;
 
     /// A type that can be used to generate new identifiers in this domain. Can be
     /// any type that implements either [`GenerateIdStateless`] or [`GenerateIdStateful`].
     ///
+    /// Generators may also implement [`GenerateIdNamed`] or [`GenerateIdFromContent`] to deterministically
+    /// generate identifiers from names or content.
+    ///
+    /// Stateful generators that can run out of identifiers may implement [`TryGenerateIdStateful`]
+    /// as well, like the [`crate::CounterGen`] provided by this crate.
+    ///
     /// Can also be `()` if random ID generation is not desired.
     ///
     /// For most cases, you can declare an empty struct and use your algorithm of choice
@@ -58,6 +80,89 @@ pub trait IdDomain {
         Id::new(from_value.into())
     }
 
+    /// Cleans up raw input before it is parsed by [`IdDomain::canonicalize`], e.g. by changing its case.
+    ///
+    /// Defaults to trimming surrounding whitespace.
+    fn normalize(input: &str) -> Cow<'_, str> {
+        Cow::Borrowed(input.trim())
+    }
+
+    /// Checks whether a backing value is a valid identifier in this domain, e.g. by checking its length.
+    ///
+    /// Used by [`IdDomain::canonicalize`]. Defaults to accepting every value.
+    fn validate(backing: &Self::Backing) -> Result<(), IdError> {
+        let _ = backing;
+        Ok(())
+    }
+
+    /// Checks every identifier with [`IdDomain::validate`], collecting all failures along with
+    /// the index of the offending identifier, e.g. for reporting problems after a bulk import.
+    fn validate_all(ids: &[Id<Self>]) -> Result<(), Vec<(usize, IdError)>>
+    where
+        Self: Sized,
+    {
+        let failures: Vec<_> = ids
+            .iter()
+            .enumerate()
+            .filter_map(|(index, id)| Self::validate(id.backing()).err().map(|err| (index, err)))
+            .collect();
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Turns untrusted input into a backing value, by applying [`IdDomain::normalize`],
+    /// parsing the result and checking it with [`IdDomain::validate`], in that order.
+    fn canonicalize(input: &str) -> Result<Self::Backing, IdError>
+    where
+        Self: Sized,
+        Self::Backing: FromStr,
+        <Self::Backing as FromStr>::Err: Display,
+    {
+        let normalized = Self::normalize(input);
+        let backing = normalized
+            .parse()
+            .map_err(|err| IdError::parse::<Self>(input, err))?;
+        Self::validate(&backing)?;
+        Ok(backing)
+    }
+
+    /// Construct a new identifier from untrusted input, see [`IdDomain::canonicalize`].
+    fn try_new_id(input: &str) -> Result<Id<Self>, IdError>
+    where
+        Self: Sized,
+        Self::Backing: FromStr,
+        <Self::Backing as FromStr>::Err: Display,
+    {
+        Self::canonicalize(input).map(Id::new)
+    }
+
+    /// Construct a new identifier from a backing value, without any implicit conversion.
+    ///
+    /// Unlike [`IdDomain::new_id`], this only accepts the backing type itself:
+    /// ```compile_fail
+    /// use stable_identifier::*;
+    ///
+    /// struct Seat;
+    /// impl IdDomain for Seat {
+    ///     const NAME: &'static str = "Seat";
+    ///     type Backing = u32;
+    ///     type Generator = ();
+    ///     type ConstRepr = ();
+    /// }
+    ///
+    /// // `char` converts into `u32`, which `new_id` would happily accept.
+    /// let seat_id = Seat::new_id_exact('a');
+    /// ```
+    fn new_id_exact(value: Self::Backing) -> Id<Self>
+    where
+        Self: Sized,
+    {
+        Id::new(value)
+    }
+
     /// Generate a new identifier.
     fn generate_id() -> Id<Self>
     where
@@ -67,6 +172,15 @@ pub trait IdDomain {
         Self::Generator::generate_id()
     }
 
+    /// Generate a new identifier, passing options like a length to the generator.
+    fn generate_id_with<Opts>(opts: Opts) -> Id<Self>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdWith<Self, Opts>,
+    {
+        Self::Generator::generate_id_with(opts)
+    }
+
     /// Generate an identifier using a given stateful generator.
     fn generate_id_stateful(generator: &mut Self::Generator) -> Id<Self>
     where
@@ -75,6 +189,399 @@ pub trait IdDomain {
     {
         generator.generate_id_stateful()
     }
+
+    /// Generate the identifier belonging to a name, which is the same every time for the same name.
+    fn generate_named_id(name: impl AsRef<[u8]>) -> Id<Self>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdNamed<Self>,
+    {
+        Self::Generator::generate_named_id(name.as_ref())
+    }
+
+    /// Derive the identifier belonging to some content, which is the same every time for the same content.
+    fn id_from_content(content: impl AsRef<[u8]>) -> Id<Self>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdFromContent<Self>,
+    {
+        Self::Generator::id_from_content(content.as_ref())
+    }
+
+    /// Endless iterator of newly generated identifiers, e.g. `D::generate_iter().take(5)`.
+    fn generate_iter() -> impl Iterator<Item = Id<Self>>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdStateless<Self>,
+    {
+        std::iter::repeat_with(Self::generate_id)
+    }
+
+    /// Endless iterator of identifiers generated using a given stateful generator.
+    fn generate_iter_stateful(generator: &mut Self::Generator) -> impl Iterator<Item = Id<Self>>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdStateful<Self>,
+    {
+        std::iter::repeat_with(move || generator.generate_id_stateful())
+    }
+
+    /// Lazily generates `count` identifiers, only generating each one when it is iterated over.
+    fn generate_batch(count: usize) -> GenerateBatch<Self, fn() -> Id<Self>>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdStateless<Self>,
+    {
+        GenerateBatch::new(count, Self::generate_id)
+    }
+
+    /// Lazily generates `count` identifiers using a given stateful generator,
+    /// only generating each one when it is iterated over.
+    fn generate_batch_stateful(
+        generator: &mut Self::Generator,
+        count: usize,
+    ) -> GenerateBatch<Self, impl FnMut() -> Id<Self>>
+    where
+        Self: Sized,
+        Self::Generator: GenerateIdStateful<Self>,
+    {
+        GenerateBatch::new(count, move || generator.generate_id_stateful())
+    }
+
+    /// Attempt to generate an identifier using a given fallible stateful generator.
+    fn try_generate_id_stateful(generator: &mut Self::Generator) -> Result<Id<Self>, GenerateError>
+    where
+        Self: Sized,
+        Self::Generator: TryGenerateIdStateful<Self>,
+    {
+        generator.try_generate_id_stateful()
+    }
+
+    /// The smallest possible identifier of this domain, useful as a lower bound in range queries.
+    ///
+    /// Only available for backings implementing [`BoundedBacking`].
+    fn min_id() -> Id<Self>
+    where
+        Self: Sized,
+        Self::Backing: BoundedBacking,
+    {
+        Id::new(Self::Backing::MIN)
+    }
+
+    /// The largest possible identifier of this domain, useful as an upper bound in range queries.
+    ///
+    /// Only available for backings implementing [`BoundedBacking`].
+    fn max_id() -> Id<Self>
+    where
+        Self: Sized,
+        Self::Backing: BoundedBacking,
+    {
+        Id::new(Self::Backing::MAX)
+    }
+
+    /// Decides whether two identifiers of this domain are equal, used by the [`PartialEq`] implementation of [`Id`].
+    ///
+    /// Defaults to comparing the backings with `==`. Override this for normalized comparison, e.g. ignoring case.
+    /// When overriding, also override [`IdDomain::hash_backing`] so that equal identifiers hash the same,
+    /// and [`IdDomain::cmp_backing`] and [`IdDomain::partial_cmp_backing`] so that equal identifiers compare as
+    /// [`Equal`](std::cmp::Ordering::Equal),
+    /// otherwise identifiers will misbehave as keys in hash maps and ordered collections.
+    fn eq_backing(a: &Self::Backing, b: &Self::Backing) -> bool
+    where
+        Self::Backing: PartialEq,
+    {
+        a == b
+    }
+
+    /// Hashes an identifier of this domain, used by the [`Hash`](std::hash::Hash) implementation of [`Id`].
+    ///
+    /// Defaults to hashing the backing. Must be consistent with [`IdDomain::eq_backing`].
+    fn hash_backing<H: std::hash::Hasher>(backing: &Self::Backing, state: &mut H)
+    where
+        Self::Backing: std::hash::Hash,
+    {
+        std::hash::Hash::hash(backing, state);
+    }
+
+    /// Orders two identifiers of this domain, used by the [`Ord`] implementation of [`Id`].
+    ///
+    /// Defaults to comparing the backings with [`Ord::cmp`]. Must be consistent with [`IdDomain::eq_backing`],
+    /// i.e. return [`Equal`](std::cmp::Ordering::Equal) exactly for equal identifiers.
+    fn cmp_backing(a: &Self::Backing, b: &Self::Backing) -> std::cmp::Ordering
+    where
+        Self::Backing: Ord,
+    {
+        a.cmp(b)
+    }
+
+    /// Partially orders two identifiers of this domain, used by the [`PartialOrd`] implementation of [`Id`].
+    ///
+    /// Defaults to comparing the backings with [`PartialOrd::partial_cmp`]. Must be consistent with
+    /// [`IdDomain::eq_backing`] and, for backings implementing [`Ord`], with [`IdDomain::cmp_backing`].
+    fn partial_cmp_backing(a: &Self::Backing, b: &Self::Backing) -> Option<std::cmp::Ordering>
+    where
+        Self::Backing: PartialOrd,
+    {
+        a.partial_cmp(b)
+    }
+
+    /// A consistent prefix/target for log output concerning identifiers of this domain.
+    ///
+    /// Defaults to [`IdDomain::NAME`].
+    fn log_target() -> &'static str {
+        Self::NAME
+    }
+
+    /// Creates a `tracing` span at debug level, with a `domain` field set to [`IdDomain::log_target`].
+    ///
+    /// Only available with the `tracing` feature.
+    #[cfg(feature = "tracing")]
+    fn span() -> tracing::Span {
+        tracing::debug_span!("id_domain", domain = Self::log_target())
+    }
+
+    /// Describes this domain at runtime, useful for generic tooling and debug output.
+    fn metadata() -> IdDomainMeta
+    where
+        Self::Generator: 'static,
+        Self::ConstRepr: 'static,
+    {
+        IdDomainMeta {
+            name: Self::NAME,
+            description: Self::DESCRIPTION,
+            category: Self::CATEGORY,
+            backing_type_name: std::any::type_name::<Self::Backing>(),
+            has_generator: !is_unit::<Self::Generator>(),
+            has_const_repr: !is_unit::<Self::ConstRepr>(),
+        }
+    }
+
+    /// Lists the type name and stable type ID of every type registered in this domain with
+    /// [`register_stable_type_id!`](crate::register_stable_type_id), sorted by type name.
+    ///
+    /// Useful for documenting the identifier space. Requires the `inventory` feature.
+    #[cfg(feature = "inventory")]
+    fn type_id_entries() -> Vec<(&'static str, Id<Self>)>
+    where
+        Self: Sized + 'static,
+    {
+        crate::registry::entries_of()
+    }
+}
+
+/// A domain with a known, finite set of valid identifiers, e.g. one identifier per variant of an enum.
+///
+/// Useful for exhaustive validation, or for listing every option in a user interface.
+pub trait FiniteDomain: IdDomain + Sized {
+    /// Every valid identifier in this domain.
+    fn all_ids() -> Vec<Id<Self>>;
+
+    /// Checks whether `id` is one of the valid identifiers of this domain.
+    fn is_member(id: &Id<Self>) -> bool
+    where
+        Self::Backing: PartialEq,
+    {
+        Self::all_ids().iter().any(|valid| valid == id)
+    }
+}
+
+/// A domain of integer identifiers that are only valid within certain ranges, e.g. the disjoint blocks
+/// of identifiers reserved for each tenant of a multi-tenant system.
+///
+/// Declaring the ranges doesn't change validation by itself: call [`RangeConstrainedDomain::validate_range`]
+/// from [`IdDomain::validate`] to reject identifiers outside of them. Use [`RangeConstrainedDomain::range_gen`]
+/// to only generate identifiers within one of the ranges.
+/// ```
+/// use std::ops::Range;
+/// use stable_identifier::*;
+///
+/// struct Invoice;
+/// impl IdDomain for Invoice {
+///     const NAME: &'static str = "Invoice";
+///     type Backing = u64;
+///     type Generator = RangeGen;
+///     type ConstRepr = ();
+///
+///     fn validate(backing: &u64) -> Result<(), IdError> {
+///         Self::validate_range(backing)
+///     }
+/// }
+/// impl RangeConstrainedDomain for Invoice {
+///     const ALLOWED_RANGES: &'static [Range<u64>] = &[1_000..2_000, 5_000..6_000];
+/// }
+///
+/// assert!(Invoice::try_new_id("1500").is_ok());
+/// assert!(Invoice::try_new_id("2500").is_err());
+///
+/// let mut second_tenant = Invoice::range_gen(1);
+/// assert_eq!(Invoice::generate_id_stateful(&mut second_tenant), Invoice::new_id(5_000u64));
+/// ```
+pub trait RangeConstrainedDomain: IdDomain + Sized {
+    /// The ranges identifiers are allowed to be in.
+    const ALLOWED_RANGES: &'static [Range<u64>];
+
+    /// Checks whether `backing` is within one of [`RangeConstrainedDomain::ALLOWED_RANGES`].
+    fn validate_range(backing: &Self::Backing) -> Result<(), IdError>
+    where
+        Self::Backing: Copy + Into<u64>,
+    {
+        let value: u64 = (*backing).into();
+        if Self::ALLOWED_RANGES
+            .iter()
+            .any(|range| range.contains(&value))
+        {
+            Ok(())
+        } else {
+            Err(IdError::invalid::<Self>(format!(
+                "{value} is outside of the allowed ranges"
+            )))
+        }
+    }
+
+    /// A generator that only hands out identifiers within the allowed range at `index`.
+    ///
+    /// Panics if there is no allowed range at `index`.
+    fn range_gen(index: usize) -> RangeGen {
+        RangeGen::new(Self::ALLOWED_RANGES[index].clone())
+    }
+}
+
+/// Declares a domain marker struct, implements [`IdDomain`] for it and adds a type alias for its identifiers,
+/// which is the usual setup for a new kind of identifier.
+///
+/// `generator` and `const_repr` are optional and default to `()`:
+/// ```
+/// use stable_identifier::*;
+///
+/// define_id!(pub Dog, "Dog", backing = String => DogId);
+/// define_id!(Ticket, "Ticket", backing = u32, generator = CounterGen<u32> => TicketId);
+///
+/// let rex: DogId = Dog::new_id("rex");
+/// assert_eq!(rex.to_string(), "Dog [rex]");
+///
+/// let mut tickets = CounterGen::default();
+/// let first: TicketId = Ticket::generate_id_stateful(&mut tickets);
+/// assert_eq!(first, Ticket::new_id(0u32));
+/// ```
+#[macro_export]
+macro_rules! define_id {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $domain:ident, $name:literal, backing = $backing:ty
+        $(, generator = $generator:ty)?
+        $(, const_repr = $const_repr:ty)?
+        => $alias:ident
+    ) => {
+        $(#[$meta])*
+        $vis struct $domain;
+
+        impl $crate::IdDomain for $domain {
+            const NAME: &'static str = $name;
+            type Backing = $backing;
+            type Generator = $crate::define_id!(@or [$($generator)?] [()]);
+            type ConstRepr = $crate::define_id!(@or [$($const_repr)?] [()]);
+        }
+
+        $vis type $alias = $crate::Id<$domain>;
+    };
+
+    (@or [$given:ty] [$default:ty]) => { $given };
+    (@or [] [$default:ty]) => { $default };
+}
+
+/// A totally ordered backing type with a smallest and largest value, see [`IdDomain::min_id`].
+///
+/// Implemented for signed and unsigned integers, and for [`Ulid`](https://docs.rs/ulid) with the `ulid` feature.
+/// These make it easy to build ranges over ordered collections:
+/// ```
+/// use std::collections::BTreeMap;
+/// use stable_identifier::*;
+///
+/// struct Order;
+/// impl IdDomain for Order {
+///     const NAME: &'static str = "Order";
+///     type Backing = u32;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// let mut orders = BTreeMap::new();
+/// orders.insert(Order::new_id(10u32), "pending");
+/// orders.insert(Order::new_id(20u32), "shipped");
+///
+/// let after_ten: Vec<_> = orders.range(Order::new_id(11u32)..=Order::max_id()).collect();
+/// assert_eq!(after_ten.len(), 1);
+/// ```
+pub trait BoundedBacking: Ord {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_bounded_backing {
+    ($($ty:ty),*) => {
+        $(
+            impl BoundedBacking for $ty {
+                const MIN: Self = <$ty>::MIN;
+                const MAX: Self = <$ty>::MAX;
+            }
+        )*
+    };
+}
+
+impl_bounded_backing!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// A backing type whose [`Display`] form has a known maximum length in bytes, see [`Id::max_display_len`].
+///
+/// Lets identifiers be formatted into fixed-size stack buffers without allocating. Implemented for
+/// integers, and for `TinyId`, `BinId`, [`Uuid`](https://docs.rs/uuid) and [`Ulid`](https://docs.rs/ulid)
+/// with their respective features.
+pub trait BoundedDisplay {
+    /// The maximum length in bytes of the [`Display`] form, without any padding from formatter flags.
+    const MAX_DISPLAY_LEN: usize;
+}
+
+macro_rules! impl_bounded_display {
+    (unsigned: $($ty:ty),*) => {
+        $(
+            impl BoundedDisplay for $ty {
+                const MAX_DISPLAY_LEN: usize = <$ty>::MAX.ilog10() as usize + 1;
+            }
+        )*
+    };
+    (signed: $($ty:ty),*) => {
+        $(
+            impl BoundedDisplay for $ty {
+                // One more for the minus sign of the minimum value.
+                const MAX_DISPLAY_LEN: usize = <$ty>::MAX.ilog10() as usize + 2;
+            }
+        )*
+    };
+}
+
+impl_bounded_display!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_bounded_display!(signed: i8, i16, i32, i64, i128, isize);
+
+/// Runtime description of an [`IdDomain`], see [`IdDomain::metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdDomainMeta {
+    /// The [`IdDomain::NAME`] of the domain.
+    pub name: &'static str,
+    /// The [`IdDomain::DESCRIPTION`] of the domain.
+    pub description: &'static str,
+    /// The [`IdDomain::CATEGORY`] of the domain.
+    pub category: &'static str,
+    /// Type name of [`IdDomain::Backing`], as given by [`std::any::type_name`].
+    pub backing_type_name: &'static str,
+    /// Whether [`IdDomain::Generator`] is something other than `()`.
+    pub has_generator: bool,
+    /// Whether [`IdDomain::ConstRepr`] is something other than `()`.
+    pub has_const_repr: bool,
+}
+
+fn is_unit<T: ?Sized + 'static>() -> bool {
+    std::any::TypeId::of::<T>() == std::any::TypeId::of::<()>()
 }
 
 #[cfg(test)]
@@ -105,4 +612,340 @@ mod tests {
         // compiler does not allow eq because Id<Dog> and Id<Cat> are different types :)
         // assert_eq!(dog_id, cat_id);
     }
+
+    #[test]
+    fn exact_constructor() {
+        struct Seat;
+        impl IdDomain for Seat {
+            const NAME: &'static str = "Seat";
+            type Backing = u32;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        assert_eq!(Seat::new_id_exact(97), Seat::new_id('a'));
+    }
+
+    #[test]
+    fn canonicalize_input() {
+        struct Username;
+        impl IdDomain for Username {
+            const NAME: &'static str = "Username";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+
+            fn normalize(input: &str) -> Cow<'_, str> {
+                Cow::Owned(input.trim().to_lowercase())
+            }
+
+            fn validate(backing: &String) -> Result<(), IdError> {
+                if backing.len() > 8 {
+                    return Err(IdError::invalid::<Self>("longer than 8 characters"));
+                }
+                Ok(())
+            }
+        }
+
+        assert_eq!(
+            Username::canonicalize("  Alice \n"),
+            Ok("alice".to_string())
+        );
+        assert_eq!(Username::try_new_id("BOB"), Ok(Username::new_id("bob")));
+        assert_eq!(
+            Username::try_new_id(" Bartholomew "),
+            Err(IdError::Invalid {
+                domain: "Username",
+                reason: "longer than 8 characters".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn validate_all_reports_every_failure() {
+        struct Sku;
+        impl IdDomain for Sku {
+            const NAME: &'static str = "Sku";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+
+            fn validate(backing: &String) -> Result<(), IdError> {
+                if !backing.starts_with("SKU-") {
+                    return Err(IdError::invalid::<Self>("missing SKU- prefix"));
+                }
+                Ok(())
+            }
+        }
+
+        let ids = ["SKU-1", "2", "SKU-3", "sku-4"].map(Sku::new_id);
+        let failures = Sku::validate_all(&ids).unwrap_err();
+        assert_eq!(
+            failures.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+            [1, 3]
+        );
+        assert_eq!(
+            failures[0].1,
+            IdError::invalid::<Sku>("missing SKU- prefix")
+        );
+
+        assert_eq!(Sku::validate_all(&ids[..1]), Ok(()));
+        assert_eq!(Sku::validate_all(&[]), Ok(()));
+    }
+
+    #[test]
+    fn canonicalize_parse_error() {
+        struct Seat;
+        impl IdDomain for Seat {
+            const NAME: &'static str = "Seat";
+            type Backing = u32;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        assert_eq!(Seat::try_new_id(" 12 "), Ok(Seat::new_id(12u32)));
+        let err = Seat::try_new_id("twelve").unwrap_err();
+        assert_eq!(err.domain(), "Seat");
+        assert!(matches!(err, IdError::Parse { .. }));
+    }
+
+    #[test]
+    fn partial_order_only_backing() {
+        struct Weight;
+        impl IdDomain for Weight {
+            const NAME: &'static str = "Weight";
+            type Backing = f64;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        assert!(Weight::new_id(1.5) < Weight::new_id(2.0));
+        assert_eq!(
+            Weight::new_id(f64::NAN).partial_cmp(&Weight::new_id(1.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn custom_equality() {
+        use std::{
+            cmp::Ordering,
+            collections::{BTreeSet, HashSet},
+            hash::{Hash, Hasher},
+        };
+
+        struct Email;
+        impl IdDomain for Email {
+            const NAME: &'static str = "Email";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+
+            fn eq_backing(a: &String, b: &String) -> bool {
+                a.eq_ignore_ascii_case(b)
+            }
+
+            fn hash_backing<H: Hasher>(backing: &String, state: &mut H) {
+                backing.to_ascii_lowercase().hash(state);
+            }
+
+            fn cmp_backing(a: &String, b: &String) -> Ordering {
+                a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase())
+            }
+
+            fn partial_cmp_backing(a: &String, b: &String) -> Option<Ordering> {
+                Some(Self::cmp_backing(a, b))
+            }
+        }
+
+        let lower = Email::new_id("anna@example.com");
+        let mixed = Email::new_id("Anna@Example.com");
+        assert_eq!(lower, mixed);
+        assert_eq!(lower.cmp(&mixed), Ordering::Equal);
+        assert_eq!(lower.partial_cmp(&mixed), Some(Ordering::Equal));
+        assert_ne!(lower, Email::new_id("anton@example.com"));
+
+        let emails: HashSet<_> = [lower.clone(), mixed.clone()].into_iter().collect();
+        assert_eq!(emails.len(), 1);
+        assert!(emails.contains(&Email::new_id("ANNA@EXAMPLE.COM")));
+
+        let sorted: BTreeSet<_> = [
+            Email::new_id("bob@example.com"),
+            mixed.clone(),
+            lower.clone(),
+            Email::new_id("Anton@example.com"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(sorted.len(), 3);
+        assert!(sorted.contains(&Email::new_id("BOB@example.com")));
+
+        assert_eq!(lower.stable_hash(), mixed.stable_hash());
+        assert_eq!(lower.shard(16), mixed.shard(16));
+    }
+
+    #[test]
+    fn define_id_macro() {
+        crate::define_id!(Dog, "Dog", backing = String => DogId);
+        crate::define_id!(
+            /// A tool identified by its kind.
+            Tool, "Tool", backing = String, const_repr = &'static str => ToolId
+        );
+
+        let rex: DogId = Dog::new_id("rex");
+        assert_eq!(rex, Dog::new_id("rex"));
+        assert_ne!(rex, Dog::new_id("fido"));
+        assert!(!Dog::metadata().has_generator);
+
+        struct Saw;
+        use crate::StableTypeId;
+        impl StableTypeId<Tool> for Saw {
+            const STABLE_TYPE_ID: &'static str = "saw";
+        }
+        let saw: ToolId = Saw::stable_type_id();
+        assert_eq!(saw.backing(), "saw");
+        assert!(Tool::metadata().has_const_repr);
+    }
+
+    #[test]
+    fn log_target_is_name() {
+        struct Dog;
+        impl IdDomain for Dog {
+            const NAME: &'static str = "Dog";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        assert_eq!(Dog::log_target(), Dog::NAME);
+    }
+
+    #[test]
+    fn finite_domain() {
+        struct Color;
+        impl IdDomain for Color {
+            const NAME: &'static str = "Color";
+            type Backing = &'static str;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+        impl FiniteDomain for Color {
+            fn all_ids() -> Vec<Id<Self>> {
+                ["red", "green", "blue"].map(Color::new_id).to_vec()
+            }
+        }
+
+        assert_eq!(Color::all_ids().len(), 3);
+        assert!(Color::is_member(&Color::new_id("green")));
+        assert!(!Color::is_member(&Color::new_id("purple")));
+    }
+
+    #[test]
+    fn metadata_without_generator() {
+        struct Dog;
+        impl IdDomain for Dog {
+            const NAME: &'static str = "Dog";
+            type Backing = u64;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let meta = Dog::metadata();
+        assert_eq!(meta.name, "Dog");
+        assert_eq!(meta.backing_type_name, "u64");
+        assert!(!meta.has_generator);
+        assert!(!meta.has_const_repr);
+        assert_eq!(meta.description, "");
+        assert_eq!(meta.category, "");
+    }
+
+    #[test]
+    fn metadata_labels() {
+        struct Invoice;
+        impl IdDomain for Invoice {
+            const NAME: &'static str = "Invoice";
+            const DESCRIPTION: &'static str = "An invoice sent to a customer.";
+            const CATEGORY: &'static str = "billing";
+            type Backing = u64;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let meta = Invoice::metadata();
+        assert_eq!(meta.description, "An invoice sent to a customer.");
+        assert_eq!(meta.category, "billing");
+    }
+
+    #[test]
+    fn integer_display_bounds() {
+        assert_eq!(u8::MAX_DISPLAY_LEN, u8::MAX.to_string().len());
+        assert_eq!(u64::MAX_DISPLAY_LEN, u64::MAX.to_string().len());
+        assert_eq!(u128::MAX_DISPLAY_LEN, u128::MAX.to_string().len());
+        assert_eq!(i8::MAX_DISPLAY_LEN, i8::MIN.to_string().len());
+        assert_eq!(i64::MAX_DISPLAY_LEN, i64::MIN.to_string().len());
+        assert_eq!(i128::MAX_DISPLAY_LEN, i128::MIN.to_string().len());
+    }
+
+    #[test]
+    fn signed_backing() {
+        struct Offset;
+        impl IdDomain for Offset {
+            const NAME: &'static str = "Offset";
+            type Backing = i64;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let negative = Offset::new_id(-5i64);
+        let positive = Offset::new_id(3i64);
+        assert_eq!(negative.backing().to_string(), "-5");
+        assert_eq!(negative.to_string(), "Offset [-5]");
+
+        let mut ids = vec![positive, Offset::new_id(0i64), negative];
+        ids.sort();
+        assert_eq!(ids, [negative, Offset::new_id(0i64), positive]);
+
+        assert_eq!(Offset::min_id(), Offset::new_id(i64::MIN));
+        assert_eq!(Offset::max_id(), Offset::new_id(i64::MAX));
+        assert!(Offset::min_id() < negative && positive < Offset::max_id());
+        assert_eq!(negative.shard(8), Offset::new_id(-5i64).shard(8));
+    }
+
+    #[test]
+    fn reject_ids_outside_ranges() {
+        struct Order;
+        impl IdDomain for Order {
+            const NAME: &'static str = "Order";
+            type Backing = u64;
+            type Generator = RangeGen;
+            type ConstRepr = ();
+
+            fn validate(backing: &u64) -> Result<(), IdError> {
+                Self::validate_range(backing)
+            }
+        }
+        impl RangeConstrainedDomain for Order {
+            const ALLOWED_RANGES: &'static [Range<u64>] = &[100..200, 300..302];
+        }
+
+        assert_eq!(Order::try_new_id("150"), Ok(Order::new_id(150u64)));
+        assert_eq!(Order::try_new_id("301"), Ok(Order::new_id(301u64)));
+        assert_eq!(
+            Order::try_new_id("200"),
+            Err(IdError::invalid::<Order>(
+                "200 is outside of the allowed ranges"
+            ))
+        );
+        assert!(Order::try_new_id("99").is_err());
+
+        let mut generator = Order::range_gen(1);
+        let generated: Vec<_> =
+            std::iter::from_fn(|| Order::try_generate_id_stateful(&mut generator).ok()).collect();
+        assert_eq!(generated, [Order::new_id(300u64), Order::new_id(301u64)]);
+        assert!(
+            generated
+                .iter()
+                .all(|id| Order::validate(id.backing()).is_ok())
+        );
+    }
 }
@@ -0,0 +1,90 @@
+use std::fmt::Display;
+
+use crate::IdDomain;
+
+/// Reasons why an identifier could not be constructed from some input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IdError {
+    /// The input could not be parsed as the backing type of the domain.
+    Parse {
+        /// [`IdDomain::NAME`] of the domain.
+        domain: &'static str,
+        /// The input that was being parsed.
+        input: String,
+        /// Why parsing failed.
+        reason: String,
+    },
+    /// The value was rejected by [`IdDomain::validate`].
+    Invalid {
+        /// [`IdDomain::NAME`] of the domain.
+        domain: &'static str,
+        /// Why the value was rejected.
+        reason: String,
+    },
+    /// An identifier could not be read from an environment variable, see [`Id::from_env`](crate::Id::from_env).
+    Env {
+        /// [`IdDomain::NAME`] of the domain.
+        domain: &'static str,
+        /// Name of the environment variable.
+        var: String,
+        /// Why the variable could not be used, e.g. because it is unset or fails to parse.
+        reason: String,
+    },
+}
+
+impl IdError {
+    /// Shorthand for an [`IdError::Invalid`] error in domain `D`.
+    pub fn invalid<D: IdDomain>(reason: impl Into<String>) -> Self {
+        IdError::Invalid {
+            domain: D::NAME,
+            reason: reason.into(),
+        }
+    }
+
+    /// Shorthand for an [`IdError::Parse`] error in domain `D`.
+    pub fn parse<D: IdDomain>(input: impl Into<String>, reason: impl Display) -> Self {
+        IdError::Parse {
+            domain: D::NAME,
+            input: input.into(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// [`IdDomain::NAME`] of the domain the error occurred in.
+    pub fn domain(&self) -> &'static str {
+        match self {
+            IdError::Parse { domain, .. }
+            | IdError::Invalid { domain, .. }
+            | IdError::Env { domain, .. } => domain,
+        }
+    }
+}
+
+impl Display for IdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdError::Parse {
+                domain,
+                input,
+                reason,
+            } => write!(
+                f,
+                "could not parse {input:?} as {domain} identifier: {reason}"
+            ),
+            IdError::Invalid { domain, reason } => {
+                write!(f, "invalid {domain} identifier: {reason}")
+            }
+            IdError::Env {
+                domain,
+                var,
+                reason,
+            } => write!(
+                f,
+                "could not read {domain} identifier from environment variable {var}: {reason}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IdError {}
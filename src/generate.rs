@@ -1,4 +1,12 @@
-use crate::{Id, IdDomain};
+use std::{
+    fmt::Display,
+    hash::{BuildHasher, RandomState},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::Range,
+};
+
+use crate::{Id, IdDomain, StableTypeId, crockford};
 
 /// Allows a type to generate identifiers in a 'stateless' manner.
 pub trait GenerateIdStateless<D: IdDomain> {
@@ -13,3 +21,826 @@ pub trait GenerateIdStateful<D: IdDomain> {
     /// Generates a new stable identifier using the state of `self`.
     fn generate_id_stateful(&mut self) -> Id<D>;
 }
+
+/// Allows a type to generate identifiers in a 'stateless' manner, taking per-call options like a length.
+///
+/// A generator can implement this for several option types.
+pub trait GenerateIdWith<D: IdDomain, Opts> {
+    /// Generates a new stable identifier according to `opts`.
+    fn generate_id_with(opts: Opts) -> Id<D>;
+}
+
+/// Allows a type to deterministically generate identifiers from a name.
+///
+/// Generating an identifier from the same name must always produce the same identifier,
+/// which makes assigning identifiers idempotent.
+pub trait GenerateIdNamed<D: IdDomain> {
+    /// Generates the stable identifier belonging to `name`.
+    fn generate_named_id(name: &[u8]) -> Id<D>;
+}
+
+/// Allows a type to derive identifiers from arbitrary content, making them content-addressed.
+///
+/// Identical content must always produce the same identifier.
+pub trait GenerateIdFromContent<D: IdDomain> {
+    /// Generates the stable identifier belonging to `content`.
+    fn id_from_content(content: &[u8]) -> Id<D>;
+}
+
+/// Allows a type to generate identifiers in a 'stateful' manner, where generation can fail.
+///
+/// Useful for generators that can run out of identifiers, like a counter reaching its maximum value.
+pub trait TryGenerateIdStateful<D: IdDomain> {
+    /// Attempts to generate a new stable identifier using the state of `self`.
+    fn try_generate_id_stateful(&mut self) -> Result<Id<D>, GenerateError>;
+}
+
+/// Generator that namespaces identifiers under a parent identifier, producing identifiers of
+/// the form `{parent}/{generated}`, e.g. comments under a post.
+///
+/// The generated part is made by the stateful generator `G`. The parent can be of any domain.
+/// Like with [`PrefixedGen`], fixed-size backings will truncate the result if it does not fit.
+///
+/// ```
+/// use stable_identifier::*;
+///
+/// define_id!(Post, "Post", backing = String => PostId);
+/// define_id!(Comment, "Comment", backing = String => CommentId);
+///
+/// struct Numbered(u32);
+/// impl GenerateIdStateful<Comment> for Numbered {
+///     fn generate_id_stateful(&mut self) -> CommentId {
+///         self.0 += 1;
+///         Comment::new_id(self.0.to_string())
+///     }
+/// }
+///
+/// let post = Post::new_id("hello-world");
+/// let mut comments = ScopedGen::new(&post, Numbered(0));
+/// let first: CommentId = comments.generate_id_stateful();
+/// assert_eq!(first.backing(), "hello-world/1");
+/// ```
+pub struct ScopedGen<'p, P: IdDomain, G> {
+    parent: &'p Id<P>,
+    inner: G,
+}
+
+impl<'p, P: IdDomain, G> ScopedGen<'p, P, G> {
+    /// Separator placed between the parent and the generated part.
+    pub const SEPARATOR: char = '/';
+
+    /// Wraps a stateful generator, scoping its identifiers under `parent`.
+    pub fn new(parent: &'p Id<P>, inner: G) -> Self {
+        Self { parent, inner }
+    }
+
+    /// The identifier generated identifiers are scoped under.
+    pub fn parent(&self) -> &'p Id<P> {
+        self.parent
+    }
+
+    /// Returns the wrapped generator.
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+impl<P, G, D> GenerateIdStateful<D> for ScopedGen<'_, P, G>
+where
+    P: IdDomain,
+    P::Backing: Display,
+    D: IdDomain,
+    D::Backing: Display + for<'a> From<&'a str>,
+    G: GenerateIdStateful<D>,
+{
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        let generated = self.inner.generate_id_stateful();
+        let text = format!(
+            "{}{}{}",
+            self.parent.backing(),
+            Self::SEPARATOR,
+            generated.backing()
+        );
+        Id::new(D::Backing::from(text.as_str()))
+    }
+}
+
+/// Reasons why a fallible generator could not produce an identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerateError {
+    /// The generator has reached the largest value its backing type can represent.
+    Overflow,
+    /// The generator has handed out all identifiers available to it.
+    Exhausted,
+}
+
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::Overflow => write!(f, "identifier generator overflowed"),
+            GenerateError::Exhausted => write!(f, "identifier generator is exhausted"),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+/// Generator that prepends the stable type ID of `P` to identifiers made by the generator `G`,
+/// producing identifiers of the form `{type_id}-{generated}`.
+///
+/// Meant for string-like backings such as [`String`] or `TinyId`. Keep in mind that fixed-size
+/// backings will truncate the result if the prefix and generated part do not fit together.
+///
+/// Implements [`GenerateIdStateless`] or [`GenerateIdStateful`] depending on what `G` implements.
+pub struct PrefixedGen<P, G> {
+    inner: G,
+    _prefix: PhantomData<P>,
+}
+
+impl<P, G> PrefixedGen<P, G> {
+    /// Separator placed between the prefix and the generated part.
+    pub const SEPARATOR: char = '-';
+
+    /// Wraps a (stateful) generator.
+    pub fn new(inner: G) -> Self {
+        Self {
+            inner,
+            _prefix: PhantomData,
+        }
+    }
+
+    fn prefixed<D>(generated: Id<D>) -> Id<D>
+    where
+        D: IdDomain,
+        D::Backing: From<D::ConstRepr> + Display + for<'a> From<&'a str>,
+        D::ConstRepr: Display,
+        P: StableTypeId<D>,
+    {
+        let text = format!(
+            "{}{}{}",
+            P::STABLE_TYPE_ID,
+            Self::SEPARATOR,
+            generated.backing()
+        );
+        Id::new(D::Backing::from(text.as_str()))
+    }
+}
+
+impl<P, G, D> GenerateIdStateless<D> for PrefixedGen<P, G>
+where
+    D: IdDomain,
+    D::Backing: From<D::ConstRepr> + Display + for<'a> From<&'a str>,
+    D::ConstRepr: Display,
+    P: StableTypeId<D>,
+    G: GenerateIdStateless<D>,
+{
+    fn generate_id() -> Id<D> {
+        Self::prefixed(G::generate_id())
+    }
+}
+
+impl<P, G, D> GenerateIdStateful<D> for PrefixedGen<P, G>
+where
+    D: IdDomain,
+    D::Backing: From<D::ConstRepr> + Display + for<'a> From<&'a str>,
+    D::ConstRepr: Display,
+    P: StableTypeId<D>,
+    G: GenerateIdStateful<D>,
+{
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        Self::prefixed(self.inner.generate_id_stateful())
+    }
+}
+
+/// What a [`CounterGen`] should do once it has handed out the maximum value of its backing type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Refuse to generate more identifiers. [`TryGenerateIdStateful`] returns
+    /// [`GenerateError::Overflow`], while [`GenerateIdStateful`] panics.
+    #[default]
+    Error,
+    /// Start over from zero. Previously generated identifiers will be handed out again!
+    Wrap,
+    /// Keep handing out the maximum value. Every identifier after the first one at the limit is a duplicate!
+    Saturate,
+}
+
+/// Unsigned integer types that can be used as the backing of a [`CounterGen`].
+///
+/// Signed integers are deliberately not supported, as counting up from zero would leave
+/// half of their range unused.
+pub trait Counter: Copy {
+    /// The first value handed out by a fresh counter.
+    const ZERO: Self;
+
+    /// Returns the value following `self`, or `None` if `self` is the maximum value.
+    fn checked_next(self) -> Option<Self>;
+}
+
+macro_rules! impl_counter {
+    ($($int:ty),*) => {
+        $(
+            impl Counter for $int {
+                const ZERO: Self = 0;
+
+                fn checked_next(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_counter!(u8, u16, u32, u64, u128, usize);
+
+/// Stateful generator that hands out increasing integer identifiers, starting from zero.
+///
+/// The behavior when the counter reaches the maximum value of the backing type is
+/// determined by its [`OverflowPolicy`].
+#[derive(Debug, Clone)]
+pub struct CounterGen<T> {
+    next: Option<T>,
+    policy: OverflowPolicy,
+}
+
+impl<T: Counter> CounterGen<T> {
+    /// Creates a counter starting from zero.
+    pub fn new(policy: OverflowPolicy) -> Self {
+        Self::starting_at(T::ZERO, policy)
+    }
+
+    /// Creates a counter that hands out `next` as its first value, e.g. when resuming from a persisted counter.
+    pub fn starting_at(next: T, policy: OverflowPolicy) -> Self {
+        Self {
+            next: Some(next),
+            policy,
+        }
+    }
+
+    fn advance(&mut self) -> Result<T, GenerateError> {
+        let current = self.next.ok_or(GenerateError::Overflow)?;
+        self.next = match (current.checked_next(), self.policy) {
+            (Some(next), _) => Some(next),
+            (None, OverflowPolicy::Error) => None,
+            (None, OverflowPolicy::Wrap) => Some(T::ZERO),
+            (None, OverflowPolicy::Saturate) => Some(current),
+        };
+        Ok(current)
+    }
+}
+
+impl<T: Counter> Default for CounterGen<T> {
+    fn default() -> Self {
+        Self::new(OverflowPolicy::default())
+    }
+}
+
+impl<T, D> TryGenerateIdStateful<D> for CounterGen<T>
+where
+    T: Counter,
+    D: IdDomain<Backing = T>,
+{
+    fn try_generate_id_stateful(&mut self) -> Result<Id<D>, GenerateError> {
+        self.advance().map(Id::new)
+    }
+}
+
+impl<T, D> GenerateIdStateful<D> for CounterGen<T>
+where
+    T: Counter,
+    D: IdDomain<Backing = T>,
+{
+    /// Panics if the counter has overflowed using [`OverflowPolicy::Error`].
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        match self.advance() {
+            Ok(value) => Id::new(value),
+            Err(err) => panic!("{err} in domain {}", D::NAME),
+        }
+    }
+}
+
+/// Stateful generator that hands out the values of a pre-allocated range, e.g. a block of
+/// identifiers reserved from a central allocator.
+///
+/// Once the range is used up, generation fails with [`GenerateError::Exhausted`] until
+/// the generator is given a new range with [`RangeGen::refill`].
+#[derive(Debug, Clone)]
+pub struct RangeGen {
+    range: Range<u64>,
+}
+
+impl RangeGen {
+    /// Creates a generator handing out the values of `range`.
+    pub fn new(range: Range<u64>) -> Self {
+        Self { range }
+    }
+
+    /// Replaces the remaining values with a new range.
+    pub fn refill(&mut self, range: Range<u64>) {
+        self.range = range;
+    }
+
+    /// The number of values left to hand out.
+    pub fn remaining(&self) -> u64 {
+        self.range.end.saturating_sub(self.range.start)
+    }
+}
+
+impl<D> TryGenerateIdStateful<D> for RangeGen
+where
+    D: IdDomain,
+    D::Backing: From<u64>,
+{
+    fn try_generate_id_stateful(&mut self) -> Result<Id<D>, GenerateError> {
+        self.range
+            .next()
+            .map(|value| Id::new(value.into()))
+            .ok_or(GenerateError::Exhausted)
+    }
+}
+
+impl<D> GenerateIdStateful<D> for RangeGen
+where
+    D: IdDomain,
+    D::Backing: From<u64>,
+{
+    /// Panics if the range is exhausted.
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        match self.try_generate_id_stateful() {
+            Ok(id) => id,
+            Err(err) => panic!("{err} in domain {}", D::NAME),
+        }
+    }
+}
+
+/// Stateful generator for string-like backings that hands out readable, deterministic labels like
+/// `user_0001`, `user_0002`, ..., made of a prefix and a zero-padded counter starting at 1.
+///
+/// Meant for test fixtures and demos, where predictable identifiers are easier to work with than random ones.
+/// Counters that need more digits than the padding width are written in full.
+/// ```
+/// use stable_identifier::*;
+///
+/// define_id!(User, "User", backing = String => UserId);
+///
+/// let mut generator = SequentialLabelGen::new("user_", 4);
+/// let first: UserId = generator.generate_id_stateful();
+/// assert_eq!(first.backing(), "user_0001");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SequentialLabelGen {
+    prefix: String,
+    width: usize,
+    next: u64,
+}
+
+impl SequentialLabelGen {
+    /// Creates a generator writing `prefix` followed by the counter, padded with zeros to `width` digits.
+    pub fn new(prefix: impl Into<String>, width: usize) -> Self {
+        Self {
+            prefix: prefix.into(),
+            width,
+            next: 1,
+        }
+    }
+}
+
+impl<D> GenerateIdStateful<D> for SequentialLabelGen
+where
+    D: IdDomain,
+    D::Backing: for<'a> From<&'a str>,
+{
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        let label = format!("{}{:0width$}", self.prefix, self.next, width = self.width);
+        self.next += 1;
+        Id::new(label.as_str().into())
+    }
+}
+
+/// Stateful generator for string-like backings whose identifiers always sort strictly after the ones
+/// generated before them, even across process restarts. Useful when paginating by identifier.
+///
+/// Each identifier is 21 characters of [Crockford base32](crate::crockford): a counter in the first 13,
+/// followed by 8 characters derived from a per-process random key, so identifiers are not trivially
+/// guessable from their neighbours. The random part is not cryptographically secure. The identifiers fit
+/// a `TinyId`, or any other backing that can be made from a `&str`.
+///
+/// ## Persistence
+/// Ordering across restarts only holds if the counter never goes backwards. Persist
+/// [`MonotonicGen::high_water_mark`] durably before handing out the identifiers generated so far, and create the
+/// generator of the next process with [`MonotonicGen::resume`] from the persisted value. To avoid a write for every
+/// identifier, persist a mark ahead of the current one and resume from that instead, skipping the unused values.
+///
+/// ```
+/// use stable_identifier::*;
+///
+/// define_id!(Page, "Page", backing = String, generator = MonotonicGen => PageId);
+///
+/// let mut generator = MonotonicGen::new();
+/// let first = Page::generate_id_stateful(&mut generator);
+/// let persisted = generator.high_water_mark();
+///
+/// let mut after_restart = MonotonicGen::resume(persisted);
+/// assert!(Page::generate_id_stateful(&mut after_restart) > first);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MonotonicGen {
+    next: u64,
+    key: RandomState,
+}
+
+impl MonotonicGen {
+    /// Creates a generator starting from the lowest counter value.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a generator continuing from a [`MonotonicGen::high_water_mark`] persisted by a previous one.
+    pub fn resume(high_water_mark: u64) -> Self {
+        Self {
+            next: high_water_mark,
+            key: RandomState::new(),
+        }
+    }
+
+    /// The counter value to persist, see the [persistence requirements](MonotonicGen#persistence).
+    pub fn high_water_mark(&self) -> u64 {
+        self.next
+    }
+
+    fn advance(&mut self) -> Result<String, GenerateError> {
+        let counter = self.next;
+        self.next = counter.checked_add(1).ok_or(GenerateError::Overflow)?;
+        let random = self.key.hash_one(counter);
+        Ok(crockford::encode(&counter.to_be_bytes())
+            + &crockford::encode(&random.to_be_bytes()[..5]))
+    }
+}
+
+impl<D> TryGenerateIdStateful<D> for MonotonicGen
+where
+    D: IdDomain,
+    D::Backing: for<'a> From<&'a str>,
+{
+    fn try_generate_id_stateful(&mut self) -> Result<Id<D>, GenerateError> {
+        self.advance().map(|text| Id::new(text.as_str().into()))
+    }
+}
+
+impl<D> GenerateIdStateful<D> for MonotonicGen
+where
+    D: IdDomain,
+    D::Backing: for<'a> From<&'a str>,
+{
+    /// Panics once the counter has reached its maximum value.
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        match self.try_generate_id_stateful() {
+            Ok(id) => id,
+            Err(err) => panic!("{err} in domain {}", D::NAME),
+        }
+    }
+}
+
+/// A batch of identifiers that are only generated as they are iterated over,
+/// see [`IdDomain::generate_batch`] and [`IdDomain::generate_batch_stateful`].
+///
+/// Unlike collecting into a `Vec`, nothing is allocated, and identifiers that are never
+/// taken are never generated.
+pub struct GenerateBatch<D, G> {
+    generate: G,
+    remaining: usize,
+    _domain: PhantomData<D>,
+}
+
+impl<D, G> GenerateBatch<D, G>
+where
+    D: IdDomain,
+    G: FnMut() -> Id<D>,
+{
+    pub(crate) fn new(count: usize, generate: G) -> Self {
+        Self {
+            generate,
+            remaining: count,
+            _domain: PhantomData,
+        }
+    }
+}
+
+impl<D, G> Iterator for GenerateBatch<D, G>
+where
+    D: IdDomain,
+    G: FnMut() -> Id<D>,
+{
+    type Item = Id<D>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some((self.generate)())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<D, G> ExactSizeIterator for GenerateBatch<D, G>
+where
+    D: IdDomain,
+    G: FnMut() -> Id<D>,
+{
+}
+
+impl<D, G> FusedIterator for GenerateBatch<D, G>
+where
+    D: IdDomain,
+    G: FnMut() -> Id<D>,
+{
+}
+
+/// Approximate number of random identifiers that can be generated before the probability of
+/// at least one collision reaches 1%, following the birthday bound.
+///
+/// Helps picking a safe length for randomly generated identifiers made of `length` characters
+/// out of an alphabet of `alphabet_size` characters. Saturates if the number of possible
+/// identifiers exceeds `u128::MAX`.
+/// ```
+/// use stable_identifier::*;
+///
+/// // 8 hexadecimal characters are only good for about 9000 identifiers.
+/// assert_eq!(collision_estimate(16, 8), 9_291);
+/// ```
+pub const fn collision_estimate(alphabet_size: usize, length: usize) -> u128 {
+    let mut possible: u128 = 1;
+    let mut i = 0;
+    while i < length {
+        possible = possible.saturating_mul(alphabet_size as u128);
+        i += 1;
+    }
+    // n ≈ sqrt(2 * possible * ln(1 / (1 - 0.01))), where 2 * ln(1 / 0.99) ≈ 0.0201.
+    match possible.checked_mul(201) {
+        Some(scaled) => (scaled / 10_000).isqrt(),
+        None => (possible / 10_000 * 201).isqrt(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashSet,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    use super::*;
+
+    struct Part;
+    impl IdDomain for Part {
+        const NAME: &'static str = "Part";
+        type Backing = String;
+        type Generator = PrefixedGen<Bolt, NextNumber>;
+        type ConstRepr = &'static str;
+    }
+
+    struct Bolt;
+    impl StableTypeId<Part> for Bolt {
+        const STABLE_TYPE_ID: &'static str = "bolt";
+    }
+
+    struct NextNumber(u64);
+    impl GenerateIdStateless<Part> for NextNumber {
+        fn generate_id() -> Id<Part> {
+            static NEXT: AtomicU64 = AtomicU64::new(0);
+            Part::new_id(NEXT.fetch_add(1, Ordering::Relaxed).to_string())
+        }
+    }
+    impl GenerateIdStateful<Part> for NextNumber {
+        fn generate_id_stateful(&mut self) -> Id<Part> {
+            self.0 += 1;
+            Part::new_id(self.0.to_string())
+        }
+    }
+
+    #[test]
+    fn prefixed_ids_are_unique() {
+        let ids: HashSet<_> = (0..100).map(|_| Part::generate_id()).collect();
+        assert_eq!(ids.len(), 100);
+        assert!(ids.iter().all(|id| id.backing().starts_with("bolt-")));
+
+        let mut generator = PrefixedGen::<Bolt, _>::new(NextNumber(0));
+        let ids: HashSet<_> = (0..100)
+            .map(|_| Part::generate_id_stateful(&mut generator))
+            .collect();
+        assert_eq!(ids.len(), 100);
+        assert!(ids.iter().all(|id| id.backing().starts_with("bolt-")));
+    }
+
+    #[test]
+    fn scoped_ids_share_parent() {
+        struct Post;
+        impl IdDomain for Post {
+            const NAME: &'static str = "Post";
+            type Backing = u64;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let post = Post::new_id(42u64);
+        let mut generator = ScopedGen::new(&post, NextNumber(0));
+        let ids: HashSet<Id<Part>> = (0..100).map(|_| generator.generate_id_stateful()).collect();
+        assert_eq!(ids.len(), 100);
+        assert!(ids.iter().all(|id| id.backing().starts_with("42/")));
+        assert!(ids.contains(&Part::new_id("42/1")));
+        assert_eq!(generator.parent(), &post);
+    }
+
+    #[test]
+    fn generator_iterators() {
+        let ids: HashSet<_> = Part::generate_iter().take(5).collect();
+        assert_eq!(ids.len(), 5);
+
+        let mut generator = CounterGen::<u8>::default();
+        let ids: Vec<_> = Ticket::generate_iter_stateful(&mut generator)
+            .take(5)
+            .map(Id::into_backing)
+            .collect();
+        assert_eq!(ids, [0, 1, 2, 3, 4]);
+        assert_eq!(Ticket::generate_id_stateful(&mut generator), Id::new(5));
+    }
+
+    #[test]
+    fn batches_are_lazy() {
+        let batch = Part::generate_batch(1_000_000);
+        assert_eq!(batch.len(), 1_000_000);
+        let ids: HashSet<_> = batch.take(3).collect();
+        assert_eq!(ids.len(), 3);
+
+        // Generating all of these eagerly would overflow the `u8` counter.
+        let mut generator = CounterGen::<u8>::default();
+        let ids: Vec<_> = Ticket::generate_batch_stateful(&mut generator, 1000)
+            .filter(|id| id.backing() % 2 == 1)
+            .take(3)
+            .map(Id::into_backing)
+            .collect();
+        assert_eq!(ids, [1, 3, 5]);
+        assert_eq!(Ticket::generate_id_stateful(&mut generator), Id::new(6));
+
+        assert_eq!(
+            Ticket::generate_batch_stateful(&mut generator, 2).count(),
+            2
+        );
+    }
+
+    struct Ticket;
+    impl IdDomain for Ticket {
+        const NAME: &'static str = "Ticket";
+        type Backing = u8;
+        type Generator = CounterGen<u8>;
+        type ConstRepr = ();
+    }
+
+    fn near_max(policy: OverflowPolicy) -> CounterGen<u8> {
+        CounterGen::starting_at(u8::MAX - 1, policy)
+    }
+
+    #[test]
+    fn counter_overflow_error() {
+        let mut generator = near_max(OverflowPolicy::Error);
+        assert_eq!(
+            Ticket::try_generate_id_stateful(&mut generator),
+            Ok(Id::new(254))
+        );
+        assert_eq!(
+            Ticket::try_generate_id_stateful(&mut generator),
+            Ok(Id::new(255))
+        );
+        assert_eq!(
+            Ticket::try_generate_id_stateful(&mut generator),
+            Err(GenerateError::Overflow)
+        );
+        assert_eq!(
+            Ticket::try_generate_id_stateful(&mut generator),
+            Err(GenerateError::Overflow)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn counter_overflow_error_panics_when_infallible() {
+        let mut generator = near_max(OverflowPolicy::Error);
+        for _ in 0..3 {
+            Ticket::generate_id_stateful(&mut generator);
+        }
+    }
+
+    #[test]
+    fn counter_overflow_wrap() {
+        let mut generator = near_max(OverflowPolicy::Wrap);
+        let ids: Vec<_> = (0..4)
+            .map(|_| *Ticket::generate_id_stateful(&mut generator).backing())
+            .collect();
+        assert_eq!(ids, [254, 255, 0, 1]);
+    }
+
+    #[test]
+    fn counter_overflow_saturate() {
+        let mut generator = near_max(OverflowPolicy::Saturate);
+        let ids: Vec<_> = (0..4)
+            .map(|_| *Ticket::generate_id_stateful(&mut generator).backing())
+            .collect();
+        assert_eq!(ids, [254, 255, 255, 255]);
+    }
+
+    struct Shipment;
+    impl IdDomain for Shipment {
+        const NAME: &'static str = "Shipment";
+        type Backing = u64;
+        type Generator = RangeGen;
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn range_exhaust_and_refill() {
+        let mut generator = RangeGen::new(10..13);
+        let ids: Vec<_> = (0..3)
+            .map(|_| {
+                *Shipment::try_generate_id_stateful(&mut generator)
+                    .unwrap()
+                    .backing()
+            })
+            .collect();
+        assert_eq!(ids, [10, 11, 12]);
+        assert_eq!(generator.remaining(), 0);
+        assert_eq!(
+            Shipment::try_generate_id_stateful(&mut generator),
+            Err(GenerateError::Exhausted)
+        );
+
+        generator.refill(1000..1002);
+        assert_eq!(generator.remaining(), 2);
+        assert_eq!(
+            Shipment::try_generate_id_stateful(&mut generator),
+            Ok(Id::new(1000))
+        );
+    }
+
+    #[test]
+    fn collision_estimates() {
+        assert_eq!(collision_estimate(10, 6), 141);
+        assert_eq!(collision_estimate(2, 0), 0);
+        assert!(collision_estimate(64, 40) > collision_estimate(64, 21));
+        assert!(collision_estimate(usize::MAX, 64) > 0);
+    }
+
+    #[test]
+    fn monotonic_across_restart() {
+        struct Page;
+        impl IdDomain for Page {
+            const NAME: &'static str = "Page";
+            type Backing = String;
+            type Generator = MonotonicGen;
+            type ConstRepr = ();
+        }
+
+        let mut generator = MonotonicGen::new();
+        let mut ids: Vec<Id<Page>> = (0..50)
+            .map(|_| Page::generate_id_stateful(&mut generator))
+            .collect();
+        let persisted = generator.high_water_mark();
+        assert_eq!(persisted, 50);
+
+        let mut restarted = MonotonicGen::resume(persisted);
+        ids.extend((0..50).map(|_| Page::generate_id_stateful(&mut restarted)));
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ids.iter().all(|id| id.backing().len() == 21));
+
+        let mut at_limit = MonotonicGen::resume(u64::MAX);
+        assert_eq!(
+            TryGenerateIdStateful::<Page>::try_generate_id_stateful(&mut at_limit),
+            Err(GenerateError::Overflow)
+        );
+    }
+
+    #[test]
+    fn sequential_labels() {
+        struct User;
+        impl IdDomain for User {
+            const NAME: &'static str = "User";
+            type Backing = String;
+            type Generator = SequentialLabelGen;
+            type ConstRepr = ();
+        }
+
+        let mut generator = SequentialLabelGen::new("user_", 4);
+        let labels: Vec<String> = User::generate_batch_stateful(&mut generator, 3)
+            .map(Id::into_backing)
+            .collect();
+        assert_eq!(labels, ["user_0001", "user_0002", "user_0003"]);
+
+        let mut narrow = SequentialLabelGen::new("n", 1);
+        let ids: Vec<Id<User>> = (0..10).map(|_| narrow.generate_id_stateful()).collect();
+        assert_eq!(ids[9].backing(), "n10");
+    }
+}
@@ -1,6 +1,7 @@
-use std::{fmt::Display, hash::Hash};
+use std::{fmt::Display, hash::Hash, str::FromStr};
 
 use super::domain::IdDomain;
+use crate::{BoundedDisplay, IdError};
 
 /// A container type for an unique identifier of an object in domain `D`.
 ///
@@ -19,6 +20,19 @@ use super::domain::IdDomain;
 /// type DogId = Id<Dog>;
 /// ```
 /// If using serde, `Id` will serialize directly as the inner type without any alteration.
+/// With the `serde-tagged` feature, it serializes as a struct holding the domain name and the backing instead.
+///
+/// For string identifiers that are cloned a lot, consider `Arc<str>` as the backing instead of `String`:
+/// cloning the identifier then only bumps a reference count instead of copying the string.
+/// It supports displaying, comparing, hashing and serde like `String` does, but has no `FromStr`
+/// implementation, so parsing functions like [`IdDomain::try_new_id`] are not available for it.
+///
+/// For short string identifiers, consider [`CompactString`](https://docs.rs/compact_str) with the
+/// `compact_str` feature: strings of up to 24 bytes are stored inline, without a heap allocation.
+///
+/// `Id` is `#[repr(transparent)]`, so it has the exact same layout and ABI as its backing type.
+/// This means it can be passed across `extern "C"` boundaries wherever the backing itself could be.
+#[repr(transparent)]
 pub struct Id<D: IdDomain> {
     backing: D::Backing,
 }
@@ -31,6 +45,49 @@ impl<D: IdDomain> Id<D> {
     pub fn into_backing(self) -> D::Backing {
         self.backing
     }
+
+    #[cfg(feature = "secrecy")]
+    pub(crate) fn backing_mut(&mut self) -> &mut D::Backing {
+        &mut self.backing
+    }
+
+    /// Alias for [`Id::backing`], following the common naming of newtype wrappers.
+    ///
+    /// ```
+    /// use stable_identifier::*;
+    ///
+    /// struct Dog;
+    /// impl IdDomain for Dog {
+    ///     const NAME: &'static str = "Dog";
+    ///     type Backing = String;
+    ///     type Generator = ();
+    ///     type ConstRepr = ();
+    /// }
+    ///
+    /// let id = Dog::new_id("fido");
+    /// assert_eq!(id.inner(), "fido");
+    /// assert_eq!(id.into_inner(), "fido".to_string());
+    /// ```
+    pub fn inner(&self) -> &D::Backing {
+        self.backing()
+    }
+
+    /// Alias for [`Id::into_backing`], following the common naming of newtype wrappers.
+    pub fn into_inner(self) -> D::Backing {
+        self.into_backing()
+    }
+
+    /// Compares the backing with a value of another type it can be compared to, without converting either.
+    ///
+    /// Handy at API boundaries, e.g. to compare a `String`-backed identifier with the backing of an
+    /// identifier borrowed as `&str`, without allocating. Unlike `==` on identifiers, this compares
+    /// the backings directly and ignores [`IdDomain::eq_backing`].
+    pub fn eq_convertible<B: ?Sized>(&self, other: &B) -> bool
+    where
+        D::Backing: PartialEq<B>,
+    {
+        self.backing == *other
+    }
 }
 
 impl<D: IdDomain> AsRef<D::Backing> for Id<D> {
@@ -50,12 +107,82 @@ where
     }
 }
 
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: BoundedDisplay,
+{
+    /// The maximum length in bytes of the [`Display`] form of identifiers in this domain, e.g. `Dog [rex]`,
+    /// for formatting into a fixed-size buffer.
+    ///
+    /// The length of the backing alone is [`BoundedDisplay::MAX_DISPLAY_LEN`].
+    pub const fn max_display_len() -> usize {
+        D::NAME.len() + " []".len() + D::Backing::MAX_DISPLAY_LEN
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: std::fmt::Debug,
+{
+    /// Renders this identifier like its [`Display`] form, using the [`Debug`](std::fmt::Debug) form of the backing.
+    ///
+    /// Useful for logging identifiers in generic code, as every backing that isn't [`Display`]
+    /// (like byte arrays) can still be printed this way.
+    pub fn display_fallback(&self) -> String {
+        format!("{} [{:?}]", D::NAME, self.backing)
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: Display,
+{
+    /// A [`Debug`](std::fmt::Debug) view of this identifier that formats the backing using [`Display`].
+    ///
+    /// `Id<D>` is only `Debug` if its backing is, and without specialization that can't fall back
+    /// to `Display`. Use this where a `Debug` value is needed for a backing that is only `Display`,
+    /// e.g. `assert_eq!(a.debug_via_display(), b.debug_via_display())` or `{:?}` in generic code.
+    pub fn debug_via_display(&self) -> DebugViaDisplay<'_, D> {
+        DebugViaDisplay(self)
+    }
+}
+
+/// Formats an identifier for [`Debug`](std::fmt::Debug) output using the [`Display`] form of its backing,
+/// see [`Id::debug_via_display`].
+pub struct DebugViaDisplay<'a, D: IdDomain>(&'a Id<D>);
+
+impl<D: IdDomain> std::fmt::Debug for DebugViaDisplay<'_, D>
+where
+    D::Backing: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple(&format!("Id<{}>", &D::NAME))
+            .field(&format_args!("{}", self.0.backing))
+            .finish()
+    }
+}
+
+impl<D: IdDomain> PartialEq for DebugViaDisplay<'_, D>
+where
+    D::Backing: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
 impl<D: IdDomain> Display for Id<D>
 where
     D::Backing: Display,
 {
+    /// Respects width, fill, alignment and precision (truncation) of the formatter,
+    /// applied to the rendered identifier as a whole.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} [{}]", D::NAME, self.backing)
+        if f.width().is_none() && f.precision().is_none() {
+            write!(f, "{} [{}]", D::NAME, self.backing)
+        } else {
+            f.pad(&format!("{} [{}]", D::NAME, self.backing))
+        }
     }
 }
 
@@ -77,18 +204,36 @@ where
     D::Backing: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.backing == other.backing
+        D::eq_backing(&self.backing, &other.backing)
     }
 }
 
 impl<D: IdDomain> Eq for Id<D> where D::Backing: Eq {}
 
+impl<D: IdDomain> PartialEq<&Id<D>> for Id<D>
+where
+    D::Backing: PartialEq,
+{
+    fn eq(&self, other: &&Id<D>) -> bool {
+        self == *other
+    }
+}
+
+impl<D: IdDomain> PartialEq<Id<D>> for &Id<D>
+where
+    D::Backing: PartialEq,
+{
+    fn eq(&self, other: &Id<D>) -> bool {
+        *self == other
+    }
+}
+
 impl<D: IdDomain> Hash for Id<D>
 where
     D::Backing: Hash,
 {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.backing.hash(state);
+        D::hash_backing(&self.backing, state);
     }
 }
 
@@ -97,7 +242,7 @@ where
     D::Backing: PartialOrd,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.backing.partial_cmp(&other.backing)
+        D::partial_cmp_backing(&self.backing, &other.backing)
     }
 }
 
@@ -106,7 +251,7 @@ where
     D::Backing: Ord,
 {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.backing.cmp(&other.backing)
+        D::cmp_backing(&self.backing, &other.backing)
     }
 }
 
@@ -115,6 +260,12 @@ mod serde {
     use crate::{domain::IdDomain, id::Id};
     use serde::{Deserialize, Serialize};
 
+    #[cfg(not(feature = "serde-tagged"))]
+    use crate::serde_helpers::transparent as default_form;
+
+    #[cfg(feature = "serde-tagged")]
+    use crate::serde_helpers::tagged as default_form;
+
     impl<D: IdDomain> Serialize for Id<D>
     where
         D::Backing: Serialize,
@@ -123,7 +274,7 @@ mod serde {
         where
             S: serde::Serializer,
         {
-            self.backing.serialize(serializer)
+            default_form::serialize(self, serializer)
         }
     }
 
@@ -135,9 +286,7 @@ mod serde {
         where
             De: serde::Deserializer<'de>,
         {
-            Ok(Id {
-                backing: D::Backing::deserialize(deserializer)?,
-            })
+            default_form::deserialize(deserializer)
         }
     }
 }
@@ -147,3 +296,439 @@ impl<D: IdDomain> Id<D> {
         Self { backing: value }
     }
 }
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: FromStr,
+    <D::Backing as FromStr>::Err: Display,
+{
+    /// Parses one identifier per line with [`IdDomain::try_new_id`], keeping going after failures.
+    ///
+    /// The results are in the same order as the lines, so the index of a failure is its line number
+    /// minus one. Empty lines are reported as failures if the domain rejects empty identifiers.
+    pub fn parse_lines(input: &str) -> Vec<Result<Self, IdError>> {
+        input.lines().map(D::try_new_id).collect()
+    }
+
+    /// Parses an identifier with [`IdDomain::try_new_id`], also accepting the [`Display`] form of the
+    /// identifier, like `Dog [rex]`, which is easily pasted by accident.
+    ///
+    /// The `"NAME ["` prefix and `"]"` suffix are only stripped if the name is the [`IdDomain::NAME`] of
+    /// this domain. Anything else, including the display form of another domain, is parsed as is.
+    pub fn parse_tolerant(input: &str) -> Result<Self, IdError> {
+        D::try_new_id(strip_display_wrapper::<D>(input.trim()))
+    }
+
+    /// Reads an identifier from the environment variable `var`, parsing it with [`IdDomain::try_new_id`].
+    ///
+    /// Fails with [`IdError::Env`], naming both the variable and the domain, if the variable is unset,
+    /// not valid unicode, or does not hold a valid identifier.
+    pub fn from_env(var: &str) -> Result<Self, IdError> {
+        Self::from_env_with(var, |var| std::env::var(var))
+    }
+
+    /// [`Id::from_env`] reading variables with `lookup`, so it can be tested without touching the
+    /// environment of the process.
+    fn from_env_with(
+        var: &str,
+        lookup: impl FnOnce(&str) -> Result<String, std::env::VarError>,
+    ) -> Result<Self, IdError> {
+        let env_error = |reason: String| IdError::Env {
+            domain: D::NAME,
+            var: var.to_owned(),
+            reason,
+        };
+        let value = lookup(var).map_err(|err| env_error(err.to_string()))?;
+        D::try_new_id(&value).map_err(|err| env_error(err.to_string()))
+    }
+}
+
+/// Strips the `"NAME ["` prefix and `"]"` suffix added by the [`Display`] implementation of [`Id`],
+/// if both are present. Anything else is returned unchanged.
+pub(crate) fn strip_display_wrapper<D: IdDomain>(input: &str) -> &str {
+    input
+        .strip_prefix(D::NAME)
+        .and_then(|rest| rest.strip_prefix(" ["))
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(input)
+}
+
+/// Constructs an identifier in `const` contexts, for any backing type.
+///
+/// [`IdDomain::new_id`] can't be `const` because it goes through [`Into`], so this is
+/// the uniform way to define identifier constants:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Planet;
+/// impl IdDomain for Planet {
+///     const NAME: &'static str = "Planet";
+///     type Backing = u64;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// const EARTH: Id<Planet> = const_id(3);
+/// ```
+pub const fn const_id<D: IdDomain>(value: D::Backing) -> Id<D> {
+    Id::new(value)
+}
+
+/// Exposes the domain of an identifier, or of a reference to one, as an associated type.
+///
+/// Generic code that receives identifiers through other traits can't always name `Id<D>` directly.
+/// It can still require identifiers to share a domain, by equating their associated `Domain` types:
+/// ```
+/// use stable_identifier::*;
+///
+/// define_id!(Dog, "Dog", backing = String => DogId);
+///
+/// fn same_dog<A, B>(a: A, b: B) -> bool
+/// where
+///     A: HasDomain,
+///     B: HasDomain<Domain = A::Domain>,
+///     <A::Domain as IdDomain>::Backing: PartialEq,
+/// {
+///     a.as_id() == b.as_id()
+/// }
+///
+/// assert!(same_dog(DogId::new("rex".into()), &DogId::new("rex".into())));
+/// ```
+/// Mixing identifiers of different domains is then rejected at compile time:
+/// ```compile_fail
+/// use stable_identifier::*;
+///
+/// define_id!(Dog, "Dog", backing = String => DogId);
+/// define_id!(Cat, "Cat", backing = String => CatId);
+///
+/// fn same_dog<A: HasDomain, B: HasDomain<Domain = A::Domain>>(a: A, b: B) {}
+///
+/// same_dog(DogId::new("rex".into()), CatId::new("rex".into()));
+/// ```
+///
+/// This trait is sealed, it is only implemented for `Id<D>` and `&Id<D>`.
+pub trait HasDomain: sealed::Sealed {
+    /// The domain of the identifier.
+    type Domain: IdDomain;
+
+    /// Borrows the identifier.
+    fn as_id(&self) -> &Id<Self::Domain>;
+}
+
+impl<D: IdDomain> HasDomain for Id<D> {
+    type Domain = D;
+
+    fn as_id(&self) -> &Id<D> {
+        self
+    }
+}
+
+impl<D: IdDomain> HasDomain for &Id<D> {
+    type Domain = D;
+
+    fn as_id(&self) -> &Id<D> {
+        self
+    }
+}
+
+mod sealed {
+    use crate::{Id, IdDomain};
+
+    pub trait Sealed {}
+
+    impl<D: IdDomain> Sealed for Id<D> {}
+    impl<D: IdDomain> Sealed for &Id<D> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dog;
+    impl IdDomain for Dog {
+        const NAME: &'static str = "Dog";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn owned_and_borrowed_eq() {
+        let owned = Dog::new_id("rex");
+        let other = Dog::new_id("rex");
+        let borrowed = &other;
+
+        assert!(owned == borrowed);
+        assert!(borrowed == owned);
+        assert!(Dog::new_id("fido") != borrowed);
+    }
+
+    #[test]
+    fn parse_tolerant_strips_own_wrapper() {
+        struct Seat;
+        impl IdDomain for Seat {
+            const NAME: &'static str = "Seat";
+            type Backing = u32;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        assert_eq!(Id::<Seat>::parse_tolerant("12"), Ok(Seat::new_id(12u32)));
+        assert_eq!(
+            Id::<Seat>::parse_tolerant(" Seat [12] "),
+            Ok(Seat::new_id(12u32))
+        );
+        assert_eq!(
+            Id::<Seat>::parse_tolerant(&Seat::new_id(7u32).to_string()),
+            Ok(Seat::new_id(7u32))
+        );
+        assert!(matches!(
+            Id::<Seat>::parse_tolerant("Table [12]"),
+            Err(IdError::Parse { input, .. }) if input == "Table [12]"
+        ));
+
+        assert_eq!(
+            Id::<Dog>::parse_tolerant("Dog [rex]"),
+            Ok(Dog::new_id("rex"))
+        );
+        assert_eq!(
+            Id::<Dog>::parse_tolerant("Cat [rex]"),
+            Ok(Dog::new_id("Cat [rex]"))
+        );
+    }
+
+    #[test]
+    fn compare_convertible_backings() {
+        struct BorrowedDog;
+        impl IdDomain for BorrowedDog {
+            const NAME: &'static str = "Dog";
+            type Backing = &'static str;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let owned = Dog::new_id("rex");
+        let borrowed = BorrowedDog::new_id("rex");
+        assert!(owned.eq_convertible(borrowed.backing()));
+        assert!(owned.eq_convertible("rex"));
+        assert!(!owned.eq_convertible(&"fido"));
+        assert!(!owned.eq_convertible(BorrowedDog::new_id("fido").backing()));
+    }
+
+    #[test]
+    fn display_padding() {
+        let id = Dog::new_id("rex");
+        assert_eq!(format!("{id}"), "Dog [rex]");
+        assert_eq!(format!("{id:>12}"), "   Dog [rex]");
+        assert_eq!(format!("{id:-<12}"), "Dog [rex]---");
+        assert_eq!(format!("{id:^11}"), " Dog [rex] ");
+        assert_eq!(format!("{id:.3}"), "Dog");
+    }
+
+    #[test]
+    fn const_table() {
+        struct Planet;
+        impl IdDomain for Planet {
+            const NAME: &'static str = "Planet";
+            type Backing = u64;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        const PLANETS: [Id<Planet>; 3] = [const_id(1), const_id(2), const_id(3)];
+        assert_eq!(PLANETS[2], Planet::new_id(3u64));
+    }
+
+    #[test]
+    fn same_layout_as_backing() {
+        use std::mem::{align_of, size_of};
+
+        struct Key;
+        impl IdDomain for Key {
+            const NAME: &'static str = "Key";
+            type Backing = [u8; 16];
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        assert_eq!(size_of::<Id<Key>>(), size_of::<[u8; 16]>());
+        assert_eq!(align_of::<Id<Key>>(), align_of::<[u8; 16]>());
+        assert_eq!(size_of::<Id<Dog>>(), size_of::<String>());
+    }
+
+    #[test]
+    fn display_fallback() {
+        struct Key;
+        impl IdDomain for Key {
+            const NAME: &'static str = "Key";
+            type Backing = [u8; 16];
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Key::new_id([1; 16]);
+        assert_eq!(
+            id.display_fallback(),
+            "Key [[1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]]"
+        );
+        assert_eq!(Dog::new_id("rex").display_fallback(), r#"Dog ["rex"]"#);
+    }
+
+    #[test]
+    fn debug_via_display() {
+        struct Slug(&'static str);
+        impl Display for Slug {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "/{}", self.0)
+            }
+        }
+        impl PartialEq for Slug {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        struct Page;
+        impl IdDomain for Page {
+            const NAME: &'static str = "Page";
+            type Backing = Slug;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Page::new_id(Slug("about"));
+        assert_eq!(format!("{:?}", id.debug_via_display()), "Id<Page>(/about)");
+        assert_eq!(
+            format!("{:#?}", id.debug_via_display()),
+            "Id<Page>(\n    /about,\n)"
+        );
+        assert_eq!(
+            id.debug_via_display(),
+            Page::new_id(Slug("about")).debug_via_display()
+        );
+        assert_eq!(format!("{:?}", Dog::new_id("rex")), r#"Id<Dog>("rex")"#);
+    }
+
+    #[test]
+    fn parse_lines_keeps_going() {
+        struct Seat;
+        impl IdDomain for Seat {
+            const NAME: &'static str = "Seat";
+            type Backing = u32;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let results = Id::<Seat>::parse_lines("12\n 7 \nA3\n40\n");
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0], Ok(Seat::new_id(12u32)));
+        assert_eq!(results[1], Ok(Seat::new_id(7u32)));
+        assert!(matches!(
+            &results[2],
+            Err(IdError::Parse { input, .. }) if input == "A3"
+        ));
+        assert_eq!(results[3], Ok(Seat::new_id(40u32)));
+    }
+
+    #[test]
+    fn from_env_names_variable_and_domain() {
+        struct Tenant;
+        impl IdDomain for Tenant {
+            const NAME: &'static str = "Tenant";
+            type Backing = u32;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        fn lookup(var: &str) -> Result<String, std::env::VarError> {
+            match var {
+                "TENANT" => Ok("17".to_owned()),
+                "TENANT_BAD" => Ok("seventeen".to_owned()),
+                _ => Err(std::env::VarError::NotPresent),
+            }
+        }
+
+        assert_eq!(
+            Id::<Tenant>::from_env_with("TENANT", lookup),
+            Ok(Tenant::new_id(17u32))
+        );
+
+        let unset = Id::<Tenant>::from_env_with("TENANT_UNSET", lookup).unwrap_err();
+        assert!(matches!(
+            &unset,
+            IdError::Env { domain: "Tenant", var, .. } if var == "TENANT_UNSET"
+        ));
+
+        let invalid = Id::<Tenant>::from_env_with("TENANT_BAD", lookup).unwrap_err();
+        let message = invalid.to_string();
+        assert!(message.contains("TENANT_BAD"));
+        assert!(message.contains("Tenant"));
+        assert!(message.contains("seventeen"));
+    }
+
+    #[test]
+    fn shared_string_backing() {
+        use std::sync::Arc;
+
+        struct Article;
+        impl IdDomain for Article {
+            const NAME: &'static str = "Article";
+            type Backing = Arc<str>;
+            type Generator = ();
+            type ConstRepr = &'static str;
+        }
+
+        let id = Article::new_id("a-long-article-slug");
+        let clone = id.clone();
+        assert!(Arc::ptr_eq(id.backing(), clone.backing()));
+        assert_eq!(id, clone);
+        assert_eq!(id.to_string(), "Article [a-long-article-slug]");
+
+        let mut articles = std::collections::HashSet::new();
+        articles.insert(clone);
+        assert!(articles.contains(&id));
+
+        #[cfg(all(feature = "serde", not(feature = "serde-tagged")))]
+        {
+            let json = serde_json::to_string(&id).unwrap();
+            assert_eq!(json, r#""a-long-article-slug""#);
+            assert_eq!(serde_json::from_str::<Id<Article>>(&json).unwrap(), id);
+        }
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn compact_string_backing() {
+        use compact_str::CompactString;
+
+        struct Tag;
+        impl IdDomain for Tag {
+            const NAME: &'static str = "Tag";
+            type Backing = CompactString;
+            type Generator = ();
+            type ConstRepr = &'static str;
+        }
+
+        let id = Tag::new_id("rust");
+        assert!(!id.backing().is_heap_allocated());
+        assert_eq!(id, Tag::new_id(CompactString::from("rust")));
+        assert_ne!(id, Tag::new_id("go"));
+        assert_eq!(id.to_string(), "Tag [rust]");
+        assert_eq!(Tag::try_new_id("rust"), Ok(id.clone()));
+
+        let mut tags = std::collections::HashSet::new();
+        tags.insert(id.clone());
+        assert!(tags.contains(&Tag::new_id("rust")));
+
+        let long = Tag::new_id("a-tag-too-long-to-be-stored-inline");
+        assert!(long.backing().is_heap_allocated());
+        assert!(long < id);
+
+        #[cfg(all(feature = "serde", not(feature = "serde-tagged")))]
+        {
+            let json = serde_json::to_string(&id).unwrap();
+            assert_eq!(json, r#""rust""#);
+            assert_eq!(serde_json::from_str::<Id<Tag>>(&json).unwrap(), id);
+        }
+    }
+}
@@ -26,17 +26,251 @@ where
     }
 }
 
+/// Lists the stable type IDs a type was known by before it was renamed, so data stored with an old ID
+/// can still be resolved to the type.
+///
+/// Unlike an alias, this is one-directional: old IDs are only accepted when reading, and are resolved to
+/// the current [`StableTypeId::STABLE_TYPE_ID`], which is the only one that should be written.
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Tool;
+/// impl IdDomain for Tool {
+///     const NAME: &'static str = "Tool";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = &'static str;
+/// }
+///
+/// struct Screwdriver;
+/// impl StableTypeId<Tool> for Screwdriver {
+///     const STABLE_TYPE_ID: &'static str = "screwdriver";
+/// }
+/// impl StableTypeIdMigration<Tool> for Screwdriver {
+///     const OLD_IDS: &'static [&'static str] = &["turnscrew"];
+/// }
+///
+/// let stored = Tool::new_id("turnscrew");
+/// assert_eq!(Screwdriver::resolve(&stored), Some(Screwdriver::stable_type_id()));
+/// ```
+pub trait StableTypeIdMigration<D>: StableTypeId<D>
+where
+    D: IdDomain,
+    D::Backing: From<D::ConstRepr>,
+    D::ConstRepr: 'static,
+{
+    /// Stable type IDs this type was previously known by, which must not be in use by any other type.
+    const OLD_IDS: &'static [D::ConstRepr];
+
+    /// Resolves `id` to the current stable type ID of this type, if `id` is either the current one
+    /// or one of [`StableTypeIdMigration::OLD_IDS`].
+    fn resolve(id: &Id<D>) -> Option<Id<D>>
+    where
+        D::ConstRepr: Clone,
+        D::Backing: PartialEq,
+    {
+        let current = Self::stable_type_id();
+        let is_known = *id == current
+            || Self::OLD_IDS
+                .iter()
+                .any(|old| *id == Id::<D>::new(old.clone().into()));
+        is_known.then_some(current)
+    }
+}
+
+/// A multi-part `const` representation of a type identifier, e.g. a category and a name.
+///
+/// Can be used as [`IdDomain::ConstRepr`] for string-backed domains, in which case the parts are
+/// joined by [`ConstPath::SEPARATOR`]:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Tool;
+/// impl IdDomain for Tool {
+///     const NAME: &'static str = "Tool";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = ConstPath<2>;
+/// }
+///
+/// struct Saw;
+/// impl StableTypeId<Tool> for Saw {
+///     const STABLE_TYPE_ID: ConstPath<2> = ConstPath(["cutting", "saw"]);
+/// }
+///
+/// assert_eq!(Saw::stable_type_id().backing(), "cutting:saw");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstPath<const N: usize>(pub [&'static str; N]);
+
+impl<const N: usize> ConstPath<N> {
+    /// Placed between the parts when joined into a single string.
+    pub const SEPARATOR: &'static str = ":";
+
+    /// Joins the parts into a single string.
+    pub fn join(&self) -> String {
+        self.0.join(Self::SEPARATOR)
+    }
+}
+
+impl<const N: usize> From<ConstPath<N>> for String {
+    fn from(path: ConstPath<N>) -> Self {
+        path.join()
+    }
+}
+
 /// Used to identify `Self` by some identifier in domain `D`.
+///
+/// With the `derive` feature, this can be derived for structs that hold an [`Id<D>`] field:
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use stable_identifier::*;
+///
+/// struct Dog;
+/// impl IdDomain for Dog {
+///     const NAME: &'static str = "Dog";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+/// type DogId = Id<Dog>;
+///
+/// #[derive(IdentifyAs)]
+/// #[identify(field = id)]
+/// struct DogRecord {
+///     id: DogId,
+///     name: String,
+/// }
+/// # }
+/// ```
+/// Without the `#[identify(field = ...)]` attribute, the single field of type `Id<...>` is used.
 pub trait IdentifyAs<D: IdDomain> {
     /// Gets a stable identifier that can be used to identify this value.
     /// In case the type has multiple identities, the domain will be inferred by pattern matching.
     fn identify_as(&self) -> Id<D>;
 }
 
+/// Declares a fieldless enum with one variant per type identified in a domain, implementing
+/// [`StableTypeId`] for each type along with the reverse lookup from identifier to variant.
+///
+/// Keeping both directions in one place means they can't drift apart. Each variant is written as
+/// `Variant for Type = STABLE_TYPE_ID`, where `Type` is an existing type:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Tool;
+/// impl IdDomain for Tool {
+///     const NAME: &'static str = "Tool";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = &'static str;
+/// }
+///
+/// struct Saw;
+/// struct Hammer;
+///
+/// stable_type_enum! {
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     pub enum ToolKind in Tool {
+///         Saw for Saw = "saw",
+///         Hammer for Hammer = "hammer",
+///     }
+/// }
+///
+/// assert_eq!(Saw::stable_type_id().backing(), "saw");
+/// assert_eq!(ToolKind::from_stable_id(&Hammer::stable_type_id()), Some(ToolKind::Hammer));
+/// assert_eq!(ToolKind::Saw.stable_id(), Saw::stable_type_id());
+/// assert_eq!(ToolKind::from_stable_id(&Tool::new_id("shovel")), None);
+/// ```
+#[macro_export]
+macro_rules! stable_type_enum {
+    (
+        $(#[$enum_meta:meta])*
+        $vis:vis enum $enum_name:ident in $domain:ty {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident for $ty:ty = $stable_id:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$enum_meta])*
+        $vis enum $enum_name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        $(
+            impl $crate::StableTypeId<$domain> for $ty {
+                const STABLE_TYPE_ID: <$domain as $crate::IdDomain>::ConstRepr = $stable_id;
+            }
+        )*
+
+        impl $enum_name {
+            /// The stable type identifier of the type belonging to this variant.
+            pub fn stable_id(&self) -> $crate::Id<$domain> {
+                match self {
+                    $(
+                        Self::$variant => <$ty as $crate::StableTypeId<$domain>>::stable_type_id(),
+                    )*
+                }
+            }
+
+            /// The variant whose type has the given stable type identifier, if any.
+            pub fn from_stable_id(id: &$crate::Id<$domain>) -> Option<Self> {
+                $(
+                    if *id == <$ty as $crate::StableTypeId<$domain>>::stable_type_id() {
+                        return Some(Self::$variant);
+                    }
+                )*
+                None
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn resolve_migrated_ids() {
+        struct Tool;
+        impl IdDomain for Tool {
+            const NAME: &'static str = "Tool";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = &'static str;
+        }
+
+        struct Spanner;
+        struct Pliers;
+
+        stable_type_enum! {
+            #[derive(Debug, PartialEq)]
+            enum ToolKind in Tool {
+                Spanner for Spanner = "spanner",
+                Pliers for Pliers = "pliers",
+            }
+        }
+
+        impl StableTypeIdMigration<Tool> for Spanner {
+            const OLD_IDS: &'static [&'static str] = &["wrench", "tools/wrench"];
+        }
+
+        let stored = Tool::new_id("tools/wrench");
+        let resolved = Spanner::resolve(&stored).unwrap();
+        assert_eq!(resolved, ToolKind::Spanner.stable_id());
+        assert_eq!(ToolKind::from_stable_id(&resolved), Some(ToolKind::Spanner));
+
+        assert_eq!(
+            Spanner::resolve(&Tool::new_id("spanner")),
+            Some(Spanner::stable_type_id())
+        );
+        assert_eq!(Spanner::resolve(&Pliers::stable_type_id()), None);
+    }
+
     #[test]
     fn id_of_types() {
         struct Tool;
@@ -70,4 +304,99 @@ mod tests {
         assert_ne!(saw_id, shovel_id);
         assert_ne!(hammer_id, shovel_id);
     }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive_identify_as() {
+        use crate::IdentifyAs;
+
+        struct Dog;
+        impl IdDomain for Dog {
+            const NAME: &'static str = "Dog";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+        type DogId = Id<Dog>;
+
+        #[derive(IdentifyAs)]
+        struct Dog1 {
+            id: Id<Dog>,
+            _name: String,
+        }
+
+        #[derive(IdentifyAs)]
+        #[identify(field = key)]
+        struct Dog2 {
+            key: DogId,
+        }
+
+        let dog = Dog1 {
+            id: Dog::new_id("rex"),
+            _name: "Rex".to_string(),
+        };
+        assert_eq!(dog.identify_as(), Dog::new_id("rex"));
+
+        let dog = Dog2 {
+            key: Dog::new_id("fido"),
+        };
+        assert_eq!(dog.identify_as(), Dog::new_id("fido"));
+    }
+
+    #[test]
+    fn multi_part_type_ids() {
+        struct Tool;
+        impl IdDomain for Tool {
+            const NAME: &'static str = "Tool";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ConstPath<2>;
+        }
+
+        struct Saw;
+        impl StableTypeId<Tool> for Saw {
+            const STABLE_TYPE_ID: ConstPath<2> = ConstPath(["cutting", "saw"]);
+        }
+
+        struct Axe;
+        impl StableTypeId<Tool> for Axe {
+            const STABLE_TYPE_ID: ConstPath<2> = ConstPath(["cutting", "axe"]);
+        }
+
+        assert_eq!(Saw::stable_type_id().backing(), "cutting:saw");
+        assert_ne!(Saw::stable_type_id(), Axe::stable_type_id());
+    }
+
+    #[test]
+    fn stable_type_enum_round_trip() {
+        struct Tool;
+        impl IdDomain for Tool {
+            const NAME: &'static str = "Tool";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ConstPath<2>;
+        }
+
+        struct Saw;
+        struct Axe;
+        struct Hammer;
+
+        crate::stable_type_enum! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            enum ToolKind in Tool {
+                Saw for Saw = ConstPath(["cutting", "saw"]),
+                Axe for Axe = ConstPath(["cutting", "axe"]),
+                Hammer for Hammer = ConstPath(["striking", "hammer"]),
+            }
+        }
+
+        for kind in [ToolKind::Saw, ToolKind::Axe, ToolKind::Hammer] {
+            assert_eq!(ToolKind::from_stable_id(&kind.stable_id()), Some(kind));
+        }
+        assert_eq!(Hammer::stable_type_id().backing(), "striking:hammer");
+        assert_eq!(
+            ToolKind::from_stable_id(&Tool::new_id("cutting:knife")),
+            None
+        );
+    }
 }
@@ -0,0 +1,65 @@
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{Id, IdDomain};
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: Serialize,
+{
+    /// Converts this identifier into a [`serde_json::Value`], in the same form it has when serialized.
+    ///
+    /// Skips formatting and re-parsing a JSON string when working with `Value`s directly:
+    /// ```
+    /// use stable_identifier::*;
+    ///
+    /// struct User;
+    /// impl IdDomain for User {
+    ///     const NAME: &'static str = "User";
+    ///     type Backing = u64;
+    ///     type Generator = ();
+    ///     type ConstRepr = ();
+    /// }
+    ///
+    /// let mut event = serde_json::json!({ "kind": "login" });
+    /// event["user"] = User::new_id(7u64).to_json_value();
+    /// # #[cfg(not(feature = "serde-tagged"))]
+    /// assert_eq!(event.to_string(), r#"{"kind":"login","user":7}"#);
+    /// ```
+    pub fn to_json_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("identifiers with a serializable backing convert to JSON")
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: DeserializeOwned,
+{
+    /// Converts a [`serde_json::Value`] made by [`Id::to_json_value`] back into an identifier.
+    pub fn try_from_json_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct User;
+    impl IdDomain for User {
+        const NAME: &'static str = "User";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn round_trip_through_value() {
+        let id = User::new_id("ada");
+        let value = id.to_json_value();
+        #[cfg(not(feature = "serde-tagged"))]
+        assert_eq!(value, serde_json::Value::from("ada"));
+        assert_eq!(Id::<User>::try_from_json_value(value).unwrap(), id);
+
+        assert!(Id::<User>::try_from_json_value(serde_json::json!([1, 2])).is_err());
+    }
+}
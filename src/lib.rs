@@ -18,20 +18,115 @@
 //!
 //! ## Optional features
 //! - `serde` lets you serialize and deserialize [`Id<T>`], as long as the backing type also implements these traits.
+//!   Alternative representations are available in `serde_helpers`.
+//! - `serde_json` adds conversion of [`Id<T>`] to and from [`serde_json::Value`](https://docs.rs/serde_json). It also adds `render_wire_json` for rendering identifiers as JSON on the wire. Implies `serde`.
+//! - `serde-tagged` makes [`Id<T>`] serialize as `{"domain": ..., "id": ...}` by default instead of as its backing,
+//!   rejecting identifiers of other domains when deserializing. Implies `serde`.
 //! - `tiny_id` provides a barebones implementation of a concrete backing type that can be used if you just want a quick and easy identifier, with random ID generation using [`nanoid`](https://docs.rs/nanoid).
+//! - `compact_str` supports [`CompactString`](https://docs.rs/compact_str) as a backing that stores short
+//!   identifiers inline, including in serde and `ProtoBacked`.
+//! - `derive` provides `#[derive(IdentifyAs)]` for structs holding an identifier.
+//! - `heapless` adds conversions between `TinyId` and [`heapless::String`](https://docs.rs/heapless). Implies `tiny_id`.
+//! - `inventory` lets you register [`StableTypeId`]s with `register_stable_type_id!` and list them per domain,
+//!   using [`inventory`](https://docs.rs/inventory).
+//! - `hash` provides content-addressed identifiers, derived by hashing content with [`blake3`](https://docs.rs/blake3).
+//! - `rusqlite` lets you store [`Id<T>`] in SQLite databases using [`rusqlite`](https://docs.rs/rusqlite).
+//! - `secrecy` lets you wrap [`Id<T>`] in [`secrecy::Secret`](https://docs.rs/secrecy), which redacts it in debug output.
+//!   See the `secret` module.
+//! - `tracing` adds helpers for creating [`tracing`](https://docs.rs/tracing) spans per domain, and a generator
+//!   wrapper that traces every generated identifier.
+//! - `ulid` provides generators for [`Ulid`](https://docs.rs/ulid) identifiers.
+//! - `url` adds percent-encoded conversion of [`Id<T>`] to and from URL path segments.
+//! - `uuid` provides generators for [`Uuid`](https://docs.rs/uuid) identifiers.
 
 // It's a small crate so might as well flatten the module hierachy.
 pub use {
-    domain::IdDomain,
-    generate::{GenerateIdStateful, GenerateIdStateless},
-    id::Id,
-    identify::{IdentifyAs, StableTypeId},
+    any::AnyId,
+    bridge::IdBridge,
+    collections::{IdMap, IdSet, remap_keys},
+    composite::CompositeBacking,
+    domain::{
+        BoundedBacking, BoundedDisplay, FiniteDomain, IdDomain, IdDomainMeta,
+        RangeConstrainedDomain,
+    },
+    error::IdError,
+    generate::{
+        Counter, CounterGen, GenerateBatch, GenerateError, GenerateIdFromContent, GenerateIdNamed,
+        GenerateIdStateful, GenerateIdStateless, GenerateIdWith, MonotonicGen, OverflowPolicy,
+        PrefixedGen, RangeGen, ScopedGen, SequentialLabelGen, TryGenerateIdStateful,
+        collision_estimate,
+    },
+    id::{DebugViaDisplay, HasDomain, Id, const_id},
+    identify::{ConstPath, IdentifyAs, StableTypeId, StableTypeIdMigration},
+    proto::{ProtoBacked, ProtoBytesError},
+    render::{Audience, Render, RenderPolicy},
+    stable_hash::{StableHasher, domain_seed, partition_by_shard},
+    versioned::{VersionedDomain, VersionedId},
 };
 
+mod any;
+mod bridge;
+mod collections;
+mod composite;
 mod domain;
+mod error;
 mod generate;
 mod id;
 mod identify;
+mod proto;
+mod render;
+mod stable_hash;
+mod versioned;
 
+#[cfg(feature = "derive")]
+pub use stable_identifier_derive::IdentifyAs;
+
+// Lets the derive macros refer to `::stable_identifier` from within this crate as well.
+extern crate self as stable_identifier;
+
+#[doc(hidden)]
+pub mod __private {
+    use crate::{Id, IdDomain};
+
+    /// Used by derive macros to find the domain of an identifier field.
+    pub trait IdField {
+        type Domain: IdDomain;
+    }
+
+    impl<D: IdDomain> IdField for Id<D> {
+        type Domain = D;
+    }
+
+    #[cfg(feature = "inventory")]
+    pub use inventory;
+}
+
+#[cfg(feature = "hash")]
+pub mod content;
+pub mod crockford;
+#[cfg(feature = "inventory")]
+mod registry;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+#[cfg(feature = "inventory")]
+pub use registry::StableTypeIdEntry;
+#[cfg(feature = "serde_json")]
+mod json_value;
+#[cfg(feature = "serde_json")]
+pub use render::render_wire_json;
+#[cfg(feature = "secrecy")]
+pub mod secret;
+#[cfg(feature = "rusqlite")]
+mod sql;
 #[cfg(feature = "tiny_id")]
 pub mod tiny_id;
+#[cfg(feature = "tracing")]
+mod tracing_gen;
+#[cfg(feature = "tracing")]
+pub use tracing_gen::TracingGen;
+#[cfg(feature = "ulid")]
+pub mod ulid_gen;
+#[cfg(feature = "url")]
+mod url_segment;
+#[cfg(feature = "uuid")]
+pub mod uuid_gen;
@@ -0,0 +1,297 @@
+use std::{fmt::Display, str::Utf8Error};
+
+use crate::{Id, IdDomain, IdError};
+
+/// A backing type with a canonical, lossless encoding as bytes, for storing identifiers in
+/// Protobuf `bytes` fields (or anywhere else raw bytes are expected).
+///
+/// The encodings of the built-in backings are:
+/// - `String`: the utf-8 bytes of the string.
+/// - [`CompactString`](https://docs.rs/compact_str): the utf-8 bytes of the string, like `String`.
+/// - `[u8; N]`: exactly its `N` bytes.
+/// - [`TinyId<N>`](crate::tiny_id::TinyId): exactly `N` bytes, the utf-8 text padded with null bytes.
+///   Decoding rejects anything but null bytes after the first null byte.
+/// - [`BinId<N>`](crate::tiny_id::BinId): exactly its `N` bytes.
+/// - [`Uuid`](https://docs.rs/uuid): exactly 16 bytes, in big-endian (RFC 4122) order.
+/// - [`Ulid`](https://docs.rs/ulid): exactly 16 bytes, in big-endian order.
+pub trait ProtoBacked: Sized {
+    /// Encodes the backing as bytes.
+    fn to_proto_bytes(&self) -> Vec<u8>;
+
+    /// Decodes bytes made by [`ProtoBacked::to_proto_bytes`].
+    fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError>;
+}
+
+/// Returned when bytes are not a valid encoding of a [`ProtoBacked`] backing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtoBytesError {
+    /// The backing has a fixed size, and the number of bytes didn't match it.
+    Length {
+        /// The number of bytes the backing is encoded as.
+        expected: usize,
+        /// The number of bytes given.
+        found: usize,
+    },
+    /// The backing is text, and the bytes were not valid utf-8.
+    Utf8(Utf8Error),
+    /// The backing is text padded with null bytes, and something other than a null byte followed the text.
+    Padding,
+}
+
+impl Display for ProtoBytesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtoBytesError::Length { expected, found } => {
+                write!(f, "expected {expected} bytes, found {found}")
+            }
+            ProtoBytesError::Utf8(err) => err.fmt(f),
+            ProtoBytesError::Padding => write!(f, "found a non-null byte in the null padding"),
+        }
+    }
+}
+
+impl std::error::Error for ProtoBytesError {}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: ProtoBacked,
+{
+    /// Encodes this identifier as bytes, see [`ProtoBacked`].
+    ///
+    /// ```
+    /// use stable_identifier::*;
+    ///
+    /// struct Dog;
+    /// impl IdDomain for Dog {
+    ///     const NAME: &'static str = "Dog";
+    ///     type Backing = String;
+    ///     type Generator = ();
+    ///     type ConstRepr = ();
+    /// }
+    ///
+    /// let bytes = Dog::new_id("rex").to_proto_bytes();
+    /// assert_eq!(bytes, b"rex");
+    /// assert_eq!(Id::<Dog>::from_proto_bytes(&bytes), Ok(Dog::new_id("rex")));
+    /// ```
+    pub fn to_proto_bytes(&self) -> Vec<u8> {
+        self.backing().to_proto_bytes()
+    }
+
+    /// Decodes an identifier from bytes made by [`Id::to_proto_bytes`].
+    pub fn from_proto_bytes(bytes: &[u8]) -> Result<Self, IdError> {
+        D::Backing::from_proto_bytes(bytes)
+            .map(Id::new)
+            .map_err(|err| IdError::parse::<D>(String::from_utf8_lossy(bytes), err))
+    }
+}
+
+impl ProtoBacked for String {
+    fn to_proto_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+        std::str::from_utf8(bytes)
+            .map(str::to_owned)
+            .map_err(ProtoBytesError::Utf8)
+    }
+}
+
+fn to_array<const N: usize>(bytes: &[u8]) -> Result<[u8; N], ProtoBytesError> {
+    bytes.try_into().map_err(|_| ProtoBytesError::Length {
+        expected: N,
+        found: bytes.len(),
+    })
+}
+
+impl<const N: usize> ProtoBacked for [u8; N] {
+    fn to_proto_bytes(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+
+    fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+        to_array(bytes)
+    }
+}
+
+#[cfg(feature = "tiny_id")]
+mod tiny_id {
+    use super::*;
+    use crate::tiny_id::{BinId, InvalidText, TinyId, check_text};
+
+    impl<const N: usize> ProtoBacked for TinyId<N> {
+        fn to_proto_bytes(&self) -> Vec<u8> {
+            self.as_bytes().to_vec()
+        }
+
+        fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+            let array = to_array::<N>(bytes)?;
+            check_text(&array).map_err(|err| match err {
+                InvalidText::Utf8(err) => ProtoBytesError::Utf8(err),
+                InvalidText::Padding => ProtoBytesError::Padding,
+            })?;
+            Ok(TinyId::from(array))
+        }
+    }
+
+    impl<const N: usize> ProtoBacked for BinId<N> {
+        fn to_proto_bytes(&self) -> Vec<u8> {
+            self.as_bytes().to_vec()
+        }
+
+        fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+            to_array::<N>(bytes).map(BinId::from)
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ProtoBacked for uuid::Uuid {
+    fn to_proto_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+        to_array::<16>(bytes).map(uuid::Uuid::from_bytes)
+    }
+}
+
+#[cfg(feature = "ulid")]
+impl ProtoBacked for ulid::Ulid {
+    fn to_proto_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+
+    fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+        to_array::<16>(bytes).map(ulid::Ulid::from_bytes)
+    }
+}
+
+#[cfg(feature = "compact_str")]
+impl ProtoBacked for compact_str::CompactString {
+    fn to_proto_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ProtoBytesError> {
+        compact_str::CompactString::from_utf8(bytes).map_err(ProtoBytesError::Utf8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<B: ProtoBacked + PartialEq + std::fmt::Debug>(backing: B, encoded: &[u8]) {
+        assert_eq!(backing.to_proto_bytes(), encoded);
+        assert_eq!(B::from_proto_bytes(encoded), Ok(backing));
+    }
+
+    #[test]
+    fn string_round_trip() {
+        round_trip("grüß".to_string(), "grüß".as_bytes());
+        assert!(matches!(
+            String::from_proto_bytes(&[0xff]),
+            Err(ProtoBytesError::Utf8(_))
+        ));
+
+        struct Dog;
+        impl IdDomain for Dog {
+            const NAME: &'static str = "Dog";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+        assert_eq!(
+            Id::<Dog>::from_proto_bytes(&[0xff]).unwrap_err().domain(),
+            "Dog"
+        );
+    }
+
+    #[test]
+    fn array_round_trip() {
+        round_trip([7u8; 4], &[7, 7, 7, 7]);
+        assert_eq!(
+            <[u8; 4]>::from_proto_bytes(&[7; 5]),
+            Err(ProtoBytesError::Length {
+                expected: 4,
+                found: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "tiny_id")]
+    #[test]
+    fn tiny_id_round_trip() {
+        use crate::tiny_id::{BinId, TinyId};
+
+        round_trip(TinyId::<6>::from("abc"), b"abc\0\0\0");
+        assert_eq!(
+            TinyId::<6>::from_proto_bytes(b"abc"),
+            Err(ProtoBytesError::Length {
+                expected: 6,
+                found: 3
+            })
+        );
+        assert!(matches!(
+            TinyId::<2>::from_proto_bytes(&[0xc3, 0]),
+            Err(ProtoBytesError::Utf8(_))
+        ));
+        assert!(matches!(
+            TinyId::<3>::from_proto_bytes(&[b'a', 0, 0xff]),
+            Err(ProtoBytesError::Padding)
+        ));
+        assert_eq!(
+            TinyId::<3>::from_proto_bytes(b"a\0b"),
+            Err(ProtoBytesError::Padding)
+        );
+
+        round_trip(BinId::from([0, 1, 2, 255]), &[0, 1, 2, 255]);
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_round_trip() {
+        let uuid = uuid::Uuid::from_u128(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff);
+        round_trip(
+            uuid,
+            &[
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff,
+            ],
+        );
+    }
+
+    #[cfg(feature = "ulid")]
+    #[test]
+    fn ulid_round_trip() {
+        let ulid = ulid::Ulid::from(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff_u128);
+        round_trip(
+            ulid,
+            &[
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff,
+            ],
+        );
+        assert_eq!(
+            ulid::Ulid::from_proto_bytes(&[0; 15]),
+            Err(ProtoBytesError::Length {
+                expected: 16,
+                found: 15
+            })
+        );
+    }
+
+    #[cfg(feature = "compact_str")]
+    #[test]
+    fn compact_string_round_trip() {
+        use compact_str::CompactString;
+
+        round_trip(CompactString::from("rex"), b"rex");
+        round_trip(CompactString::from("grüße"), "grüße".as_bytes());
+        assert!(matches!(
+            CompactString::from_proto_bytes(&[0xff]),
+            Err(ProtoBytesError::Utf8(_))
+        ));
+    }
+}
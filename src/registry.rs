@@ -0,0 +1,151 @@
+use std::any::{Any, TypeId};
+
+use crate::{Id, IdDomain, StableTypeId};
+
+/// A type registered with [`register_stable_type_id!`](crate::register_stable_type_id), see
+/// [`IdDomain::type_id_entries`].
+///
+/// Entries of all domains are collected together, and only resolved to a concrete domain when listed.
+pub struct StableTypeIdEntry {
+    domain: fn() -> TypeId,
+    type_name: fn() -> &'static str,
+    stable_type_id: fn() -> Box<dyn Any>,
+}
+
+impl StableTypeIdEntry {
+    #[doc(hidden)]
+    pub const fn new<D, T>() -> Self
+    where
+        D: IdDomain + 'static,
+        D::Backing: From<D::ConstRepr>,
+        T: StableTypeId<D>,
+    {
+        Self {
+            domain: TypeId::of::<D>,
+            type_name: std::any::type_name::<T>,
+            stable_type_id: boxed_stable_type_id::<D, T>,
+        }
+    }
+}
+
+fn boxed_stable_type_id<D, T>() -> Box<dyn Any>
+where
+    D: IdDomain + 'static,
+    D::Backing: From<D::ConstRepr>,
+    T: StableTypeId<D>,
+{
+    Box::new(T::stable_type_id())
+}
+
+inventory::collect!(StableTypeIdEntry);
+
+pub(crate) fn entries_of<D: IdDomain + 'static>() -> Vec<(&'static str, Id<D>)> {
+    let mut entries: Vec<_> = inventory::iter::<StableTypeIdEntry>
+        .into_iter()
+        .filter(|entry| (entry.domain)() == TypeId::of::<D>())
+        .map(|entry| {
+            let id = (entry.stable_type_id)()
+                .downcast::<Id<D>>()
+                .expect("registered stable type id belongs to the domain it was registered for");
+            ((entry.type_name)(), *id)
+        })
+        .collect();
+    entries.sort_by_key(|(type_name, _)| *type_name);
+    entries
+}
+
+/// Registers the [`StableTypeId`] of a type, so it is listed by [`IdDomain::type_id_entries`].
+///
+/// Requires the `inventory` feature. Can be used anywhere in the crate graph, at the item level:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Tool;
+/// impl IdDomain for Tool {
+///     const NAME: &'static str = "Tool";
+///     type Backing = &'static str;
+///     type Generator = ();
+///     type ConstRepr = &'static str;
+/// }
+///
+/// struct Hammer;
+/// impl StableTypeId<Tool> for Hammer {
+///     const STABLE_TYPE_ID: &'static str = "hammer";
+/// }
+/// register_stable_type_id!(Hammer, Tool);
+///
+/// let entries = Tool::type_id_entries();
+/// assert_eq!(entries.len(), 1);
+/// assert!(entries[0].0.ends_with("Hammer"));
+/// assert_eq!(entries[0].1, Hammer::stable_type_id());
+/// ```
+#[macro_export]
+macro_rules! register_stable_type_id {
+    ($ty:ty, $domain:ty) => {
+        $crate::__private::inventory::submit! {
+            $crate::StableTypeIdEntry::new::<$domain, $ty>()
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    struct Tool;
+    impl IdDomain for Tool {
+        const NAME: &'static str = "Tool";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = &'static str;
+    }
+
+    struct Saw;
+    impl StableTypeId<Tool> for Saw {
+        const STABLE_TYPE_ID: &'static str = "saw";
+    }
+
+    struct Drill;
+    impl StableTypeId<Tool> for Drill {
+        const STABLE_TYPE_ID: &'static str = "drill";
+    }
+
+    struct Wrench;
+    impl StableTypeId<Tool> for Wrench {
+        const STABLE_TYPE_ID: &'static str = "wrench";
+    }
+
+    struct Fruit;
+    impl IdDomain for Fruit {
+        const NAME: &'static str = "Fruit";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = &'static str;
+    }
+
+    struct Apple;
+    impl StableTypeId<Fruit> for Apple {
+        const STABLE_TYPE_ID: &'static str = "apple";
+    }
+
+    register_stable_type_id!(Saw, Tool);
+    register_stable_type_id!(Drill, Tool);
+    register_stable_type_id!(Wrench, Tool);
+    register_stable_type_id!(Apple, Fruit);
+
+    #[test]
+    fn lists_registered_types_of_domain() {
+        let entries = Tool::type_id_entries();
+        assert_eq!(
+            entries,
+            [
+                (std::any::type_name::<Drill>(), Tool::new_id("drill")),
+                (std::any::type_name::<Saw>(), Tool::new_id("saw")),
+                (std::any::type_name::<Wrench>(), Tool::new_id("wrench")),
+            ]
+        );
+        assert!(entries[0].0.ends_with("::Drill"));
+
+        assert_eq!(Fruit::type_id_entries().len(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Display;
+
+use crate::{Id, IdDomain};
+
+/// Who an identifier is rendered for, see [`Render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Audience {
+    /// Debug logs, where the domain should be obvious, e.g. `Dog [rex]`.
+    Log,
+    /// People using an application, who only care about the identifier itself.
+    User,
+    /// Other systems, e.g. in an API payload or a message queue.
+    Wire,
+}
+
+/// Per-domain policy for how identifiers are rendered for each [`Audience`].
+///
+/// The defaults render the [`Display`] form of [`Id`] for logs, and the [`Display`] form of the backing
+/// for users and on the wire. With the `serde_json` feature, `render_wire_json` renders the serde form
+/// instead, for policies that want it on the wire.
+///
+/// Override any of them to change the representation for every identifier of the domain at once:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Order;
+/// impl IdDomain for Order {
+///     const NAME: &'static str = "Order";
+///     type Backing = u64;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+/// impl RenderPolicy for Order {
+///     fn render_user(id: &Id<Self>) -> String {
+///         format!("#{:06}", id.backing())
+///     }
+/// }
+///
+/// let id = Order::new_id(42u64);
+/// assert_eq!(id.render(Audience::Log), "Order [42]");
+/// assert_eq!(id.render(Audience::User), "#000042");
+/// ```
+pub trait RenderPolicy: IdDomain + Sized
+where
+    Self::Backing: Display,
+{
+    /// Renders an identifier for [`Audience::Log`].
+    fn render_log(id: &Id<Self>) -> String {
+        id.to_string()
+    }
+
+    /// Renders an identifier for [`Audience::User`].
+    fn render_user(id: &Id<Self>) -> String {
+        id.backing().to_string()
+    }
+
+    /// Renders an identifier for [`Audience::Wire`].
+    fn render_wire(id: &Id<Self>) -> String {
+        id.backing().to_string()
+    }
+}
+
+/// Renders the serde form of an identifier as JSON, which includes the domain with `serde-tagged`.
+///
+/// Meant to be called from [`RenderPolicy::render_wire`] by domains whose backing implements `Serialize`:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Dog;
+/// impl IdDomain for Dog {
+///     const NAME: &'static str = "Dog";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+/// impl RenderPolicy for Dog {
+///     fn render_wire(id: &Id<Self>) -> String {
+///         render_wire_json(id)
+///     }
+/// }
+///
+/// let id = Dog::new_id("rex");
+/// assert_eq!(id.render(Audience::Wire), serde_json::to_string(&id).unwrap());
+/// ```
+///
+/// Only available with the `serde_json` feature.
+#[cfg(feature = "serde_json")]
+pub fn render_wire_json<D>(id: &Id<D>) -> String
+where
+    D: IdDomain,
+    D::Backing: serde::Serialize,
+{
+    serde_json::to_string(id).expect("identifiers always serialize to JSON")
+}
+
+/// Renders a value differently depending on who it is for, following the [`RenderPolicy`] of its domain.
+pub trait Render {
+    /// Renders `self` for `audience`.
+    fn render(&self, audience: Audience) -> String;
+}
+
+impl<D> Render for Id<D>
+where
+    D: RenderPolicy,
+    D::Backing: Display,
+{
+    fn render(&self, audience: Audience) -> String {
+        match audience {
+            Audience::Log => D::render_log(self),
+            Audience::User => D::render_user(self),
+            Audience::Wire => D::render_wire(self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dog;
+    impl IdDomain for Dog {
+        const NAME: &'static str = "Dog";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+    impl RenderPolicy for Dog {}
+
+    struct Invoice;
+    impl IdDomain for Invoice {
+        const NAME: &'static str = "Invoice";
+        type Backing = u32;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+    impl RenderPolicy for Invoice {
+        fn render_user(id: &Id<Self>) -> String {
+            format!("INV-{:05}", id.backing())
+        }
+
+        fn render_wire(id: &Id<Self>) -> String {
+            format!("{:08x}", id.backing())
+        }
+    }
+
+    #[test]
+    fn default_renderings() {
+        let id = Dog::new_id("rex");
+        assert_eq!(id.render(Audience::Log), "Dog [rex]");
+        assert_eq!(id.render(Audience::User), "rex");
+        assert_eq!(id.render(Audience::Wire), "rex");
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json_wire_rendering() {
+        struct Cat;
+        impl IdDomain for Cat {
+            const NAME: &'static str = "Cat";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+        impl RenderPolicy for Cat {
+            fn render_wire(id: &Id<Self>) -> String {
+                render_wire_json(id)
+            }
+        }
+
+        let id = Cat::new_id("tom");
+        let wire = id.render(Audience::Wire);
+        assert_eq!(wire, serde_json::to_string(&id).unwrap());
+        #[cfg(not(feature = "serde-tagged"))]
+        assert_eq!(wire, r#""tom""#);
+
+        let renderings =
+            [Audience::Log, Audience::User, Audience::Wire].map(|audience| id.render(audience));
+        assert_ne!(renderings[0], renderings[1]);
+        assert_ne!(renderings[1], renderings[2]);
+        assert_ne!(renderings[0], renderings[2]);
+    }
+
+    #[test]
+    fn custom_renderings() {
+        let id = Invoice::new_id(1234u32);
+        assert_eq!(id.render(Audience::Log), "Invoice [1234]");
+        assert_eq!(id.render(Audience::User), "INV-01234");
+        assert_eq!(id.render(Audience::Wire), "000004d2");
+    }
+}
@@ -0,0 +1,79 @@
+//! Lets identifiers be wrapped in [`secrecy::Secret`], e.g. for API keys or session tokens.
+//!
+//! [`Id`] implements `Zeroize` whenever its backing does, so the identifier is wiped from memory when the
+//! secret is dropped. The debug output of the secret only names the domain:
+//! ```
+//! use secrecy::{ExposeSecret, Secret};
+//! use stable_identifier::*;
+//!
+//! define_id!(Session, "Session", backing = String => SessionId);
+//!
+//! let token = Secret::new(Session::new_id("s3cr3t"));
+//! assert_eq!(format!("{token:?}"), "Secret([REDACTED Session identifier])");
+//! assert_eq!(token.expose_secret().backing(), "s3cr3t");
+//! ```
+//!
+//! Requires the `secrecy` feature.
+
+use std::fmt;
+
+use secrecy::{CloneableSecret, DebugSecret, Zeroize};
+
+use crate::{Id, IdDomain};
+
+impl<D: IdDomain> Zeroize for Id<D>
+where
+    D::Backing: Zeroize,
+{
+    fn zeroize(&mut self) {
+        self.backing_mut().zeroize();
+    }
+}
+
+impl<D: IdDomain> CloneableSecret for Id<D> where D::Backing: Clone + Zeroize {}
+
+impl<D: IdDomain> DebugSecret for Id<D> {
+    fn debug_secret(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[REDACTED {} identifier]", D::NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secrecy::{ExposeSecret, Secret};
+
+    use super::*;
+
+    struct ApiKey;
+    impl IdDomain for ApiKey {
+        const NAME: &'static str = "ApiKey";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn debug_redacts_secret_id() {
+        let key = Secret::new(ApiKey::new_id("key-123"));
+        let debug = format!("{key:?}");
+        assert!(!debug.contains("key-123"));
+        assert_eq!(debug, "Secret([REDACTED ApiKey identifier])");
+
+        let clone = key.clone();
+        assert_eq!(clone.expose_secret(), key.expose_secret());
+
+        let mut id = ApiKey::new_id("key-123");
+        id.zeroize();
+        assert!(id.backing().is_empty());
+    }
+
+    #[cfg(feature = "tiny_id")]
+    #[test]
+    fn zeroize_tiny_id() {
+        use crate::tiny_id::TinyId;
+
+        let mut id = TinyId::<8>::from("key-123");
+        id.zeroize();
+        assert_eq!(id.into_array(), [0; 8]);
+    }
+}
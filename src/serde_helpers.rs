@@ -0,0 +1,1315 @@
+//! Helpers for (de)serializing identifiers in ways other than the default transparent form,
+//! mostly meant for use with `#[serde(with = "...")]` or for specialized data pipelines.
+
+/// Streams sequences of identifiers without building an intermediate collection.
+///
+/// Useful for exporting and importing large amounts of identifiers. The identifiers are written
+/// as a plain sequence, e.g. a JSON array or a length-prefixed sequence in binary formats.
+///
+/// ```
+/// # use stable_identifier::{*, serde_helpers::stream};
+/// struct Dog;
+/// impl IdDomain for Dog {
+///     const NAME: &'static str = "Dog";
+///     type Backing = u64;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// let mut json = Vec::new();
+/// let ids = (0..3u64).map(Dog::new_id);
+/// stream::serialize(ids, &mut serde_json::Serializer::new(&mut json)).unwrap();
+/// # #[cfg(not(feature = "serde-tagged"))]
+/// assert_eq!(json, b"[0,1,2]");
+///
+/// let mut sum = 0;
+/// stream::deserialize_each(&mut serde_json::Deserializer::from_slice(&json), |id: Id<Dog>| {
+///     sum += id.backing();
+/// })
+/// .unwrap();
+/// assert_eq!(sum, 3);
+/// ```
+pub mod stream {
+    use std::{borrow::Borrow, fmt, marker::PhantomData};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{SeqAccess, Visitor},
+        ser::SerializeSeq,
+    };
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes every identifier yielded by `ids` as a sequence.
+    ///
+    /// The length of the sequence is taken from the iterator's size hint if it is exact. Formats that
+    /// require the length up-front (like most binary formats) will fail for iterators without one.
+    pub fn serialize<D, I, S>(ids: I, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Serialize,
+        I: IntoIterator,
+        I::Item: Borrow<Id<D>>,
+        S: Serializer,
+    {
+        let ids = ids.into_iter();
+        let len = match ids.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+        let mut seq = serializer.serialize_seq(len)?;
+        for id in ids {
+            seq.serialize_element(id.borrow())?;
+        }
+        seq.end()
+    }
+
+    /// Deserializes a sequence of identifiers, handing each one to `f` as soon as it is read.
+    pub fn deserialize_each<'de, D, De, F>(deserializer: De, f: F) -> Result<(), De::Error>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de>,
+        De: Deserializer<'de>,
+        F: FnMut(Id<D>),
+    {
+        deserializer.deserialize_seq(EachVisitor {
+            f,
+            _domain: PhantomData,
+        })
+    }
+
+    struct EachVisitor<D, F> {
+        f: F,
+        _domain: PhantomData<D>,
+    }
+
+    impl<'de, D, F> Visitor<'de> for EachVisitor<D, F>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de>,
+        F: FnMut(Id<D>),
+    {
+        type Value = ();
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a sequence of {} identifiers", D::NAME)
+        }
+
+        fn visit_seq<A>(mut self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            while let Some(id) = seq.next_element::<Id<D>>()? {
+                (self.f)(id);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Serializes identifiers directly as their backing, which is the default unless the `serde-tagged`
+/// feature is enabled.
+///
+/// Use with `#[serde(with = "stable_identifier::serde_helpers::transparent")]` to keep this form for
+/// specific fields regardless of features.
+pub mod transparent {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the backing as is.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Serialize,
+        S: Serializer,
+    {
+        id.backing().serialize(serializer)
+    }
+
+    /// Deserializes the backing as is.
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de>,
+        De: Deserializer<'de>,
+    {
+        D::Backing::deserialize(deserializer).map(Id::new)
+    }
+}
+
+/// Serializes identifiers as a struct holding the domain name and the backing, like `{"domain": "Dog", "id": "rex"}`,
+/// which is the default if the `serde-tagged` feature is enabled.
+///
+/// Deserializing fails if the domain name doesn't match, which catches identifiers of one domain being
+/// read as another. Use with `#[serde(with = "stable_identifier::serde_helpers::tagged")]`.
+pub mod tagged {
+    use std::{borrow::Cow, fmt, marker::PhantomData};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, MapAccess, SeqAccess, Visitor},
+        ser::SerializeStruct,
+    };
+
+    use crate::{Id, IdDomain};
+
+    const FIELDS: &[&str] = &["domain", "id"];
+
+    /// Serializes the domain name and the backing.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Serialize,
+        S: Serializer,
+    {
+        let mut tagged = serializer.serialize_struct("Id", 2)?;
+        tagged.serialize_field("domain", D::NAME)?;
+        tagged.serialize_field("id", id.backing())?;
+        tagged.end()
+    }
+
+    /// Deserializes the domain name and the backing, failing if the domain name isn't [`IdDomain::NAME`].
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de>,
+        De: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_struct("Id", FIELDS, TaggedVisitor::<D>(PhantomData))
+            .map(Id::new)
+    }
+
+    #[derive(Deserialize)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Field {
+        Domain,
+        Id,
+    }
+
+    fn check_domain<D: IdDomain, E: Error>(domain: &str) -> Result<(), E> {
+        if domain != D::NAME {
+            return Err(E::custom(format_args!(
+                "expected identifier of domain {}, found {domain}",
+                D::NAME
+            )));
+        }
+        Ok(())
+    }
+
+    struct TaggedVisitor<D>(PhantomData<D>);
+
+    impl<'de, D> Visitor<'de> for TaggedVisitor<D>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de>,
+    {
+        type Value = D::Backing;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a tagged {} identifier", D::NAME)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let domain: Cow<'de, str> = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            check_domain::<D, _>(&domain)?;
+            seq.next_element()?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut domain_seen = false;
+            let mut backing = None;
+            while let Some(field) = map.next_key()? {
+                match field {
+                    Field::Domain => {
+                        if domain_seen {
+                            return Err(A::Error::duplicate_field("domain"));
+                        }
+                        let domain: Cow<'de, str> = map.next_value()?;
+                        check_domain::<D, _>(&domain)?;
+                        domain_seen = true;
+                    }
+                    Field::Id => {
+                        if backing.is_some() {
+                            return Err(A::Error::duplicate_field("id"));
+                        }
+                        backing = Some(map.next_value()?);
+                    }
+                }
+            }
+            if !domain_seen {
+                return Err(A::Error::missing_field("domain"));
+            }
+            backing.ok_or_else(|| A::Error::missing_field("id"))
+        }
+    }
+}
+
+/// Treats an empty string or `null` as the "nil" identifier, whose backing is [`Default::default`].
+///
+/// Meant for formats where a missing identifier is represented by `""` or `null` instead of being left out,
+/// as an alternative to `Option<Id<D>>`. Use with `#[serde(with = "stable_identifier::serde_helpers::empty_as_nil")]`.
+///
+/// The nil identifier is serialized as `null`. Deserializing requires a self-describing format like JSON.
+pub mod empty_as_nil {
+    use std::{fmt, marker::PhantomData};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{IntoDeserializer, Visitor},
+    };
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the nil identifier as `null`, anything else as its backing.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Serialize + Default + PartialEq,
+        S: Serializer,
+    {
+        if *id.backing() == D::Backing::default() {
+            serializer.serialize_none()
+        } else {
+            id.backing().serialize(serializer)
+        }
+    }
+
+    /// Deserializes `""` and `null` as the nil identifier, anything else as the backing.
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de> + Default,
+        De: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(NilVisitor::<D>(PhantomData))
+            .map(Id::new)
+    }
+
+    struct NilVisitor<D>(PhantomData<D>);
+
+    impl<'de, D> Visitor<'de> for NilVisitor<D>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de> + Default,
+    {
+        type Value = D::Backing;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a {} identifier, empty string or null", D::NAME)
+        }
+
+        fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(D::Backing::default())
+        }
+
+        fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+            Ok(D::Backing::default())
+        }
+
+        fn visit_some<De: Deserializer<'de>>(
+            self,
+            deserializer: De,
+        ) -> Result<Self::Value, De::Error> {
+            deserializer.deserialize_any(self)
+        }
+
+        fn visit_borrowed_str<E: serde::de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+            if v.is_empty() {
+                return Ok(D::Backing::default());
+            }
+            D::Backing::deserialize(v.into_deserializer())
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            if v.is_empty() {
+                return Ok(D::Backing::default());
+            }
+            D::Backing::deserialize(v.to_owned().into_deserializer())
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+    }
+}
+
+/// Serializes identifiers as strings, using the [`Display`](std::fmt::Display) and [`FromStr`](std::str::FromStr)
+/// implementations of the backing.
+///
+/// Useful for numeric identifiers sent to JavaScript clients, where numbers above 2^53 lose precision.
+/// Use with `#[serde(with = "stable_identifier::serde_helpers::as_string")]`.
+///
+/// ```
+/// # use stable_identifier::{*, serde_helpers::as_string};
+/// struct Account;
+/// impl IdDomain for Account {
+///     const NAME: &'static str = "Account";
+///     type Backing = u64;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// let json = as_string::serialize(&Account::new_id(u64::MAX), serde_json::value::Serializer).unwrap();
+/// assert_eq!(json, serde_json::json!("18446744073709551615"));
+/// ```
+pub mod as_string {
+    use std::{fmt::Display, str::FromStr};
+
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the backing as a string.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Display,
+        S: Serializer,
+    {
+        serializer.collect_str(id.backing())
+    }
+
+    /// Deserializes the backing by parsing a string.
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: FromStr,
+        <D::Backing as FromStr>::Err: Display,
+        De: Deserializer<'de>,
+    {
+        let text = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        text.parse()
+            .map(Id::new)
+            .map_err(|err| De::Error::custom(format_args!("invalid {} identifier: {err}", D::NAME)))
+    }
+}
+
+/// Serializes numeric identifiers as numbers, while also accepting numeric strings when deserializing.
+///
+/// The counterpart of [`as_string`], for reading data written
+/// by either representation. Deserializing requires a self-describing format like JSON.
+/// Use with `#[serde(with = "stable_identifier::serde_helpers::as_number")]`.
+pub mod as_number {
+    use std::{fmt, marker::PhantomData, str::FromStr};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{IntoDeserializer, Visitor},
+    };
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the backing as is, i.e. as a number.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Serialize,
+        S: Serializer,
+    {
+        id.backing().serialize(serializer)
+    }
+
+    /// Deserializes the backing from either a number or a numeric string.
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de> + FromStr,
+        <D::Backing as FromStr>::Err: fmt::Display,
+        De: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(NumberVisitor::<D>(PhantomData))
+            .map(Id::new)
+    }
+
+    struct NumberVisitor<D>(PhantomData<D>);
+
+    impl<'de, D> Visitor<'de> for NumberVisitor<D>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de> + FromStr,
+        <D::Backing as FromStr>::Err: fmt::Display,
+    {
+        type Value = D::Backing;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a {} identifier as a number or string", D::NAME)
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            v.parse()
+                .map_err(|err| E::custom(format_args!("invalid {} identifier: {err}", D::NAME)))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+
+        fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+
+        fn visit_i128<E: serde::de::Error>(self, v: i128) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+    }
+}
+
+/// Accepts identifiers both in their raw backing form and in their [`Display`](std::fmt::Display) form,
+/// like `"Dog [rex]"`, when deserializing.
+///
+/// Meant for migrating logs or dumps that contain formatted identifiers back into structured data.
+/// Strings have an optional `"NAME ["` prefix and `"]"` suffix removed and are then parsed with
+/// [`FromStr`](std::str::FromStr), other values are deserialized as the backing. Identifiers are
+/// serialized in the raw form. Deserializing requires a self-describing format like JSON.
+/// Use with `#[serde(with = "stable_identifier::serde_helpers::lenient")]`.
+pub mod lenient {
+    use std::{fmt, marker::PhantomData, str::FromStr};
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{IntoDeserializer, Visitor},
+    };
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the backing as is.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Serialize,
+        S: Serializer,
+    {
+        id.backing().serialize(serializer)
+    }
+
+    /// Deserializes the backing, or parses it from the display form of the identifier.
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de> + FromStr,
+        <D::Backing as FromStr>::Err: fmt::Display,
+        De: Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_any(LenientVisitor::<D>(PhantomData))
+            .map(Id::new)
+    }
+
+    struct LenientVisitor<D>(PhantomData<D>);
+
+    impl<'de, D> Visitor<'de> for LenientVisitor<D>
+    where
+        D: IdDomain,
+        D::Backing: Deserialize<'de> + FromStr,
+        <D::Backing as FromStr>::Err: fmt::Display,
+    {
+        type Value = D::Backing;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "a {} identifier, optionally in its display form",
+                D::NAME
+            )
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            crate::id::strip_display_wrapper::<D>(v)
+                .parse()
+                .map_err(|err| E::custom(format_args!("invalid {} identifier: {err}", D::NAME)))
+        }
+
+        fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+
+        fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            D::Backing::deserialize(v.into_deserializer())
+        }
+    }
+}
+
+/// Serializes a `HashMap<Id<D>, V>` as a map keyed by the string form of the identifiers, e.g. a JSON object.
+///
+/// JSON object keys must be strings, so maps keyed by numeric identifiers can't be serialized to JSON
+/// directly. The keys are written using the [`Display`](std::fmt::Display) implementation of the backing,
+/// and parsed back with [`FromStr`](std::str::FromStr).
+/// Use with `#[serde(with = "stable_identifier::serde_helpers::id_keyed_map")]`.
+pub mod id_keyed_map {
+    use std::{
+        collections::HashMap,
+        fmt::{self, Display},
+        hash::{BuildHasher, Hash},
+        marker::PhantomData,
+        str::FromStr,
+    };
+
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer,
+        de::{Error, MapAccess, Visitor},
+    };
+
+    use crate::{Id, IdDomain};
+
+    struct Key<'a, D: IdDomain>(&'a Id<D>);
+
+    impl<D: IdDomain> Serialize for Key<'_, D>
+    where
+        D::Backing: Display,
+    {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self.0.backing())
+        }
+    }
+
+    /// Serializes the map, writing the identifiers as strings.
+    pub fn serialize<D, V, H, S>(
+        map: &HashMap<Id<D>, V, H>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: Display,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_map(map.iter().map(|(id, value)| (Key(id), value)))
+    }
+
+    /// Deserializes the map, parsing the identifiers from strings.
+    pub fn deserialize<'de, D, V, H, De>(
+        deserializer: De,
+    ) -> Result<HashMap<Id<D>, V, H>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: FromStr + Eq + Hash,
+        <D::Backing as FromStr>::Err: Display,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+        De: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+
+    struct MapVisitor<D, V, H>(PhantomData<(D, V, H)>);
+
+    impl<'de, D, V, H> Visitor<'de> for MapVisitor<D, V, H>
+    where
+        D: IdDomain,
+        D::Backing: FromStr + Eq + Hash,
+        <D::Backing as FromStr>::Err: Display,
+        V: Deserialize<'de>,
+        H: BuildHasher + Default,
+    {
+        type Value = HashMap<Id<D>, V, H>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a map keyed by {} identifiers", D::NAME)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+            let mut map =
+                HashMap::with_capacity_and_hasher(access.size_hint().unwrap_or(0), H::default());
+            while let Some((key, value)) = access.next_entry::<std::borrow::Cow<'de, str>, V>()? {
+                let backing = key.parse().map_err(|err| {
+                    A::Error::custom(format_args!("invalid {} identifier: {err}", D::NAME))
+                })?;
+                map.insert(Id::new(backing), value);
+            }
+            Ok(map)
+        }
+    }
+}
+
+/// Compresses batches of string identifiers that share common prefixes, like `user_...`, using front coding.
+///
+/// Each identifier is written as the length (in bytes) of the prefix it shares with the previous
+/// identifier, followed by the remaining suffix. Works best when the identifiers are sorted.
+/// Use with `#[serde(with = "stable_identifier::serde_helpers::front_coded")]` on a `Vec<Id<D>>`.
+///
+/// ```
+/// # use stable_identifier::{*, serde_helpers::front_coded};
+/// struct User;
+/// impl IdDomain for User {
+///     const NAME: &'static str = "User";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// let ids = vec![User::new_id("user_anna"), User::new_id("user_anton")];
+/// let json = front_coded::serialize(&ids, serde_json::value::Serializer).unwrap();
+/// assert_eq!(json, serde_json::json!([[0, "user_anna"], [7, "ton"]]));
+/// ```
+pub mod front_coded {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error, ser::SerializeSeq};
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the identifiers as a sequence of `(shared prefix length, suffix)` pairs.
+    pub fn serialize<D, S>(ids: &[Id<D>], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: AsRef<str>,
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(ids.len()))?;
+        let mut previous = "";
+        for id in ids {
+            let current = id.backing().as_ref();
+            let shared = shared_prefix_len(previous, current);
+            seq.serialize_element(&(shared, &current[shared..]))?;
+            previous = current;
+        }
+        seq.end()
+    }
+
+    /// Deserializes a sequence of `(shared prefix length, suffix)` pairs, restoring the full identifiers.
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Vec<Id<D>>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: From<String>,
+        De: Deserializer<'de>,
+    {
+        let pairs = Vec::<(usize, String)>::deserialize(deserializer)?;
+        let mut ids = Vec::with_capacity(pairs.len());
+        let mut previous = String::new();
+        for (shared, suffix) in pairs {
+            let Some(prefix) = previous.get(..shared) else {
+                return Err(De::Error::custom(format_args!(
+                    "invalid shared prefix length {shared} for {} identifier",
+                    D::NAME
+                )));
+            };
+            let current = format!("{prefix}{suffix}");
+            ids.push(Id::new(current.clone().into()));
+            previous = current;
+        }
+        Ok(ids)
+    }
+
+    /// Length in bytes of the common prefix, always ending on a char boundary.
+    fn shared_prefix_len(a: &str, b: &str) -> usize {
+        a.char_indices()
+            .zip(b.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or(a.len().min(b.len()), |((index, _), _)| index)
+    }
+}
+
+/// Writes string identifiers as a varint length followed by the UTF-8 bytes in binary formats, and as
+/// plain strings in human-readable formats.
+///
+/// The length is an unsigned LEB128 varint, taking a single byte for identifiers shorter than 128 bytes,
+/// where e.g. bincode would otherwise spend 8 bytes on the length of every string. The bytes are written
+/// as a tuple of `u8`s, so this only saves space in formats that write tuples without a length and bytes
+/// as single bytes, like bincode. Use with `#[serde(with = "stable_identifier::serde_helpers::len_prefixed")]`.
+///
+/// ```
+/// # use stable_identifier::{*, serde_helpers::len_prefixed};
+/// struct User;
+/// impl IdDomain for User {
+///     const NAME: &'static str = "User";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Session {
+///     #[serde(with = "len_prefixed")]
+///     user: Id<User>,
+/// }
+///
+/// let bytes = bincode::serialize(&Session { user: User::new_id("ada") }).unwrap();
+/// assert_eq!(bytes, [3, b'a', b'd', b'a']);
+/// ```
+pub mod len_prefixed {
+    use std::{fmt, marker::PhantomData};
+
+    use serde::{
+        Deserialize, Deserializer, Serializer,
+        de::{Error, SeqAccess, Visitor},
+        ser::SerializeTuple,
+    };
+
+    use crate::{Id, IdDomain};
+
+    /// Serializes the backing as a varint length and its bytes, or as a string in human-readable formats.
+    pub fn serialize<D, S>(id: &Id<D>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain,
+        D::Backing: AsRef<str>,
+        S: Serializer,
+    {
+        let text = id.backing().as_ref();
+        if serializer.is_human_readable() {
+            return serializer.serialize_str(text);
+        }
+
+        let mut length = [0u8; 10];
+        let mut length_len = 0;
+        let mut remaining = text.len() as u64;
+        loop {
+            let byte = (remaining & 0x7f) as u8;
+            remaining >>= 7;
+            if remaining == 0 {
+                length[length_len] = byte;
+                length_len += 1;
+                break;
+            }
+            length[length_len] = byte | 0x80;
+            length_len += 1;
+        }
+
+        let mut tuple = serializer.serialize_tuple(length_len + text.len())?;
+        for byte in length[..length_len].iter().chain(text.as_bytes()) {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+
+    /// Deserializes a backing written by [`serialize`].
+    pub fn deserialize<'de, D, De>(deserializer: De) -> Result<Id<D>, De::Error>
+    where
+        D: IdDomain,
+        D::Backing: From<String>,
+        De: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return String::deserialize(deserializer).map(|text| Id::new(text.into()));
+        }
+        // The total length is only known after reading the varint, so ask for as many elements as
+        // there could be and stop once the string is complete.
+        deserializer
+            .deserialize_tuple(usize::MAX, LenPrefixedVisitor::<D>(PhantomData))
+            .map(|text| Id::new(text.into()))
+    }
+
+    struct LenPrefixedVisitor<D>(PhantomData<D>);
+
+    impl<'de, D: IdDomain> Visitor<'de> for LenPrefixedVisitor<D> {
+        type Value = String;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a length-prefixed {} identifier", D::NAME)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut next_byte = || {
+                seq.next_element::<u8>()?.ok_or_else(|| {
+                    A::Error::custom(format_args!("truncated {} identifier", D::NAME))
+                })
+            };
+
+            let mut len: u64 = 0;
+            for shift in (0..64).step_by(7) {
+                let byte = next_byte()?;
+                let payload = u64::from(byte & 0x7f);
+                if shift == 63 && payload > 1 {
+                    break;
+                }
+                len |= payload << shift;
+                if byte & 0x80 == 0 {
+                    if byte == 0 && shift > 0 {
+                        return Err(A::Error::custom(format_args!(
+                            "length of {} identifier is not minimally encoded",
+                            D::NAME
+                        )));
+                    }
+                    let bytes = (0..len)
+                        .map(|_| next_byte())
+                        .collect::<Result<Vec<u8>, _>>()?;
+                    return String::from_utf8(bytes).map_err(|err| {
+                        A::Error::custom(format_args!("invalid {} identifier: {err}", D::NAME))
+                    });
+                }
+            }
+            Err(A::Error::custom(format_args!(
+                "length of {} identifier overflows",
+                D::NAME
+            )))
+        }
+    }
+}
+
+/// Packs sequences of [`TinyId`](crate::tiny_id::TinyId)-backed identifiers into one contiguous byte
+/// buffer of `N * len` bytes in binary formats, instead of framing every identifier on its own.
+///
+/// Each identifier takes exactly `N` bytes including its null padding, so the buffer is written with a
+/// single length prefix. Human-readable formats get a plain sequence of strings. Serializes slices and
+/// deserializes `Vec`s. Use with `#[serde(with = "stable_identifier::serde_helpers::packed_tiny_ids")]`.
+/// Requires the `tiny_id` feature.
+///
+/// ```
+/// # use stable_identifier::{*, serde_helpers::packed_tiny_ids, tiny_id::TinyId};
+/// struct Tag;
+/// impl IdDomain for Tag {
+///     const NAME: &'static str = "Tag";
+///     type Backing = TinyId<4>;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Post {
+///     #[serde(with = "packed_tiny_ids")]
+///     tags: Vec<Id<Tag>>,
+/// }
+///
+/// let post = Post { tags: vec![Tag::new_id("rust"), Tag::new_id("ids")] };
+/// let bytes = bincode::serialize(&post).unwrap();
+/// assert_eq!(bytes.len(), 8 + 2 * 4);
+/// ```
+#[cfg(feature = "tiny_id")]
+pub mod packed_tiny_ids {
+    use std::{fmt, marker::PhantomData};
+
+    use serde::{
+        Deserialize, Deserializer, Serializer,
+        de::{Error, SeqAccess, Visitor},
+        ser::SerializeSeq,
+    };
+
+    use crate::{
+        Id, IdDomain,
+        tiny_id::{TinyId, check_text},
+    };
+
+    /// Serializes the identifiers as one buffer of `N * ids.len()` bytes, or as a sequence of strings
+    /// in human-readable formats.
+    pub fn serialize<D, S, const N: usize>(ids: &[Id<D>], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        D: IdDomain<Backing = TinyId<N>>,
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let mut seq = serializer.serialize_seq(Some(ids.len()))?;
+            for id in ids {
+                seq.serialize_element(id.backing().as_str())?;
+            }
+            return seq.end();
+        }
+
+        let mut buffer = Vec::with_capacity(N * ids.len());
+        for id in ids {
+            buffer.extend_from_slice(id.backing().as_bytes());
+        }
+        serializer.serialize_bytes(&buffer)
+    }
+
+    /// Deserializes identifiers written by [`serialize`].
+    ///
+    /// Fails if a string doesn't fit in `N` bytes, or if a chunk of the buffer is not valid utf-8
+    /// padded with null bytes.
+    pub fn deserialize<'de, D, De, const N: usize>(
+        deserializer: De,
+    ) -> Result<Vec<Id<D>>, De::Error>
+    where
+        D: IdDomain<Backing = TinyId<N>>,
+        De: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let texts = Vec::<String>::deserialize(deserializer)?;
+            return texts
+                .iter()
+                .map(|text| {
+                    TinyId::try_from_str(text)
+                        .map(Id::new)
+                        .map_err(De::Error::custom)
+                })
+                .collect();
+        }
+
+        let buffer = deserializer.deserialize_byte_buf(BufferVisitor::<D>(PhantomData))?;
+        if buffer.len() % N != 0 {
+            return Err(De::Error::custom(format_args!(
+                "packed {} identifiers must be a multiple of {N} bytes, got {}",
+                D::NAME,
+                buffer.len()
+            )));
+        }
+        buffer
+            .chunks_exact(N)
+            .enumerate()
+            .map(|(index, chunk)| match check_text(chunk) {
+                Ok(()) => Ok(Id::new(TinyId::from_bytes(chunk))),
+                Err(_) => Err(De::Error::custom(format_args!(
+                    "packed {} identifier at index {index} is not utf-8 padded with null bytes",
+                    D::NAME
+                ))),
+            })
+            .collect()
+    }
+
+    struct BufferVisitor<D>(PhantomData<D>);
+
+    impl<'de, D: IdDomain> Visitor<'de> for BufferVisitor<D> {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a buffer of packed {} identifiers", D::NAME)
+        }
+
+        fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(v)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut buffer = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                buffer.push(byte);
+            }
+            Ok(buffer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::{Id, IdDomain};
+
+    struct Parcel;
+    impl IdDomain for Parcel {
+        const NAME: &'static str = "Parcel";
+        type Backing = u64;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn stream_many_ids() {
+        let mut json = Vec::new();
+        let ids = (0..10_000u64).map(Parcel::new_id);
+        stream::serialize(ids, &mut serde_json::Serializer::new(&mut json)).unwrap();
+
+        let mut read_back: Vec<Id<Parcel>> = Vec::new();
+        stream::deserialize_each(&mut serde_json::Deserializer::from_slice(&json), |id| {
+            read_back.push(id)
+        })
+        .unwrap();
+
+        assert_eq!(read_back.len(), 10_000);
+        assert!(
+            read_back
+                .iter()
+                .enumerate()
+                .all(|(index, id)| *id.backing() == index as u64)
+        );
+    }
+
+    struct Customer;
+    impl IdDomain for Customer {
+        const NAME: &'static str = "Customer";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Order {
+        #[serde(with = "empty_as_nil")]
+        customer: Id<Customer>,
+        #[serde(with = "empty_as_nil")]
+        parcel: Id<Parcel>,
+    }
+
+    #[test]
+    fn empty_and_null_are_nil() {
+        let order: Order = serde_json::from_str(r#"{"customer":"","parcel":null}"#).unwrap();
+        assert_eq!(order.customer, Customer::new_id(""));
+        assert_eq!(order.parcel, Parcel::new_id(0u64));
+        assert_eq!(
+            serde_json::to_string(&order).unwrap(),
+            r#"{"customer":null,"parcel":null}"#
+        );
+
+        let order: Order = serde_json::from_str(r#"{"customer":null,"parcel":5}"#).unwrap();
+        assert_eq!(order.customer, Customer::new_id(""));
+        assert_eq!(order.parcel, Parcel::new_id(5u64));
+
+        let order: Order = serde_json::from_str(r#"{"customer":"c-1","parcel":7}"#).unwrap();
+        assert_eq!(order.customer, Customer::new_id("c-1"));
+        assert_eq!(
+            serde_json::to_string(&order).unwrap(),
+            r#"{"customer":"c-1","parcel":7}"#
+        );
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Export {
+        #[serde(with = "front_coded")]
+        customers: Vec<Id<Customer>>,
+    }
+
+    #[test]
+    fn front_coded_round_trip() {
+        let customers: Vec<_> = (0..1000)
+            .map(|n| Customer::new_id(format!("customer_account_{n:05}")))
+            .collect();
+        let export = Export {
+            customers: customers.clone(),
+        };
+
+        let compressed = serde_json::to_string(&export).unwrap();
+        let plain = serde_json::to_string(&customers).unwrap();
+        assert!(compressed.len() * 2 < plain.len());
+
+        let read_back: Export = serde_json::from_str(&compressed).unwrap();
+        assert_eq!(read_back.customers, customers);
+    }
+
+    #[test]
+    fn front_coded_multibyte_and_invalid() {
+        let ids = vec![
+            Customer::new_id("kö"),
+            Customer::new_id("kø"),
+            Customer::new_id("k"),
+        ];
+        let json = serde_json::to_string(&Export {
+            customers: ids.clone(),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"customers":[[0,"kö"],[1,"ø"],[1,""]]}"#);
+        let read_back: Export = serde_json::from_str(&json).unwrap();
+        assert_eq!(read_back.customers, ids);
+
+        assert!(serde_json::from_str::<Export>(r#"{"customers":[[0,"kö"],[2,"x"]]}"#).is_err());
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Shipment {
+        #[serde(with = "as_string")]
+        for_js: Id<Parcel>,
+        #[serde(with = "as_number")]
+        for_rust: Id<Parcel>,
+    }
+
+    #[test]
+    fn numeric_as_string_or_number() {
+        let shipment = Shipment {
+            for_js: Parcel::new_id(u64::MAX),
+            for_rust: Parcel::new_id(u64::MAX),
+        };
+        let json = serde_json::to_string(&shipment).unwrap();
+        assert_eq!(
+            json,
+            r#"{"for_js":"18446744073709551615","for_rust":18446744073709551615}"#
+        );
+        assert_eq!(serde_json::from_str::<Shipment>(&json).unwrap(), shipment);
+
+        let lenient: Shipment =
+            serde_json::from_str(r#"{"for_js":"7","for_rust":"18446744073709551615"}"#).unwrap();
+        assert_eq!(lenient.for_js, Parcel::new_id(7u64));
+        assert_eq!(lenient.for_rust, Parcel::new_id(u64::MAX));
+
+        assert!(serde_json::from_str::<Shipment>(r#"{"for_js":7,"for_rust":7}"#).is_err());
+        assert!(serde_json::from_str::<Shipment>(r#"{"for_js":"x","for_rust":7}"#).is_err());
+    }
+
+    #[derive(Deserialize)]
+    struct LogLine {
+        #[serde(with = "lenient")]
+        customer: Id<Customer>,
+        #[serde(with = "lenient")]
+        parcel: Id<Parcel>,
+    }
+
+    #[test]
+    fn lenient_raw_and_display_forms() {
+        let raw: LogLine = serde_json::from_str(r#"{"customer":"c-1","parcel":5}"#).unwrap();
+        assert_eq!(raw.customer, Customer::new_id("c-1"));
+        assert_eq!(raw.parcel, Parcel::new_id(5u64));
+
+        let displayed: LogLine =
+            serde_json::from_str(r#"{"customer":"Customer [c-1]","parcel":"Parcel [5]"}"#).unwrap();
+        assert_eq!(displayed.customer, Customer::new_id("c-1"));
+        assert_eq!(displayed.parcel, Parcel::new_id(5u64));
+
+        let other_domain: LogLine =
+            serde_json::from_str(r#"{"customer":"Parcel [c-1]","parcel":"5"}"#).unwrap();
+        assert_eq!(other_domain.customer, Customer::new_id("Parcel [c-1]"));
+        assert_eq!(other_domain.parcel, Parcel::new_id(5u64));
+
+        assert!(
+            serde_json::from_str::<LogLine>(r#"{"customer":"","parcel":"Customer [5]"}"#).is_err()
+        );
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Inventory {
+        #[serde(with = "id_keyed_map")]
+        stock: std::collections::HashMap<Id<Parcel>, u32>,
+    }
+
+    #[test]
+    fn id_keyed_map_as_object() {
+        let inventory = Inventory {
+            stock: [(Parcel::new_id(7u64), 3)].into_iter().collect(),
+        };
+        let json = serde_json::to_string(&inventory).unwrap();
+        assert_eq!(json, r#"{"stock":{"7":3}}"#);
+
+        let read_back: Inventory = serde_json::from_str(r#"{"stock":{"7":3,"12":0}}"#).unwrap();
+        assert_eq!(read_back.stock.len(), 2);
+        assert_eq!(read_back.stock[&Parcel::new_id(12u64)], 0);
+        assert_eq!(read_back.stock[&Parcel::new_id(7u64)], 3);
+
+        assert!(serde_json::from_str::<Inventory>(r#"{"stock":{"seven":3}}"#).is_err());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Delivery {
+        #[serde(with = "tagged")]
+        parcel: Id<Parcel>,
+        #[serde(with = "transparent")]
+        customer: Id<Customer>,
+    }
+
+    #[test]
+    fn tagged_and_transparent_forms() {
+        let delivery = Delivery {
+            parcel: Parcel::new_id(5u64),
+            customer: Customer::new_id("c-1"),
+        };
+        let json = serde_json::to_string(&delivery).unwrap();
+        assert_eq!(
+            json,
+            r#"{"parcel":{"domain":"Parcel","id":5},"customer":"c-1"}"#
+        );
+        assert_eq!(serde_json::from_str::<Delivery>(&json).unwrap(), delivery);
+
+        let bytes = bincode::serialize(&delivery).unwrap();
+        assert_eq!(bincode::deserialize::<Delivery>(&bytes).unwrap(), delivery);
+
+        let wrong_domain = r#"{"parcel":{"domain":"Customer","id":5},"customer":"c-1"}"#;
+        let err = serde_json::from_str::<Delivery>(wrong_domain).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("expected identifier of domain Parcel")
+        );
+        assert!(serde_json::from_str::<Delivery>(r#"{"parcel":{"id":5},"customer":""}"#).is_err());
+    }
+
+    #[cfg(feature = "serde-tagged")]
+    #[test]
+    fn tagged_by_default() {
+        let id = Customer::new_id("c-1");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#"{"domain":"Customer","id":"c-1"}"#);
+        assert_eq!(serde_json::from_str::<Id<Customer>>(&json).unwrap(), id);
+
+        let err = serde_json::from_str::<Id<Customer>>(r#"{"domain":"Parcel","id":"c-1"}"#);
+        assert!(err.is_err());
+        assert!(serde_json::from_str::<Id<Customer>>(r#""c-1""#).is_err());
+    }
+
+    #[cfg(not(feature = "serde-tagged"))]
+    #[test]
+    fn transparent_by_default() {
+        let id = Customer::new_id("c-1");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""c-1""#);
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Login {
+        #[serde(with = "len_prefixed")]
+        customer: Id<Customer>,
+    }
+
+    #[test]
+    fn len_prefixed_binary_and_text() {
+        let login = Login {
+            customer: Customer::new_id("customer-42"),
+        };
+        let bytes = bincode::serialize(&login).unwrap();
+        assert_eq!(bytes.len(), 1 + "customer-42".len());
+        assert_eq!(bytes[0], 11);
+        assert_eq!(bincode::deserialize::<Login>(&bytes).unwrap(), login);
+        assert!(bytes.len() < bincode::serialize(&login.customer).unwrap().len());
+
+        let long = Login {
+            customer: Customer::new_id("x".repeat(300)),
+        };
+        let bytes = bincode::serialize(&long).unwrap();
+        assert_eq!(&bytes[..2], [0xac, 0x02]);
+        assert_eq!(bincode::deserialize::<Login>(&bytes).unwrap(), long);
+        assert!(bincode::deserialize::<Login>(&bytes[..100]).is_err());
+
+        let overflowing = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+        assert!(bincode::deserialize::<Login>(&overflowing).is_err());
+        assert!(bincode::deserialize::<Login>(&[0x81, 0x00, b'a']).is_err());
+
+        let json = serde_json::to_string(&login).unwrap();
+        assert_eq!(json, r#"{"customer":"customer-42"}"#);
+        assert_eq!(serde_json::from_str::<Login>(&json).unwrap(), login);
+    }
+
+    #[cfg(feature = "tiny_id")]
+    #[test]
+    fn packed_tiny_ids_single_buffer() {
+        use crate::tiny_id::TinyId;
+
+        struct Sensor;
+        impl IdDomain for Sensor {
+            const NAME: &'static str = "Sensor";
+            type Backing = TinyId<6>;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Readings {
+            #[serde(with = "packed_tiny_ids")]
+            sensors: Vec<Id<Sensor>>,
+        }
+
+        let readings = Readings {
+            sensors: (0..50)
+                .map(|n| Sensor::new_id(format!("s-{n}").as_str()))
+                .collect(),
+        };
+        let bytes = bincode::serialize(&readings).unwrap();
+        let buffer: Vec<u8> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(buffer.len(), 6 * 50);
+        assert_eq!(bytes.len(), 8 + 6 * 50);
+        assert_eq!(bincode::deserialize::<Readings>(&bytes).unwrap(), readings);
+
+        let json = serde_json::to_string(&readings).unwrap();
+        assert!(json.starts_with(r#"{"sensors":["s-0","s-1","#));
+        assert_eq!(serde_json::from_str::<Readings>(&json).unwrap(), readings);
+
+        let truncated = bincode::serialize(&vec![0u8; 7]).unwrap();
+        assert!(bincode::deserialize::<Readings>(&truncated).is_err());
+
+        let mut invalid = vec![0u8; 12];
+        invalid[..3].copy_from_slice(b"s-0");
+        invalid[6] = 0xff;
+        let invalid = bincode::serialize(&invalid).unwrap();
+        assert!(bincode::deserialize::<Readings>(&invalid).is_err());
+
+        assert!(serde_json::from_str::<Readings>(r#"{"sensors":["too-long"]}"#).is_err());
+    }
+}
@@ -0,0 +1,109 @@
+//! [`rusqlite`](https://docs.rs/rusqlite) support: identifiers are stored as their backing value.
+use rusqlite::{
+    Row, ToSql,
+    types::{FromSql, FromSqlResult, ToSqlOutput, ValueRef},
+};
+
+use crate::{Id, IdDomain};
+
+impl<D: IdDomain> ToSql for Id<D>
+where
+    D::Backing: ToSql,
+{
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        self.backing().to_sql()
+    }
+}
+
+impl<D: IdDomain> FromSql for Id<D>
+where
+    D::Backing: FromSql,
+{
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        D::Backing::column_result(value).map(Id::new)
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: FromSql,
+{
+    /// Reads an identifier from the column named `column` of a query result row.
+    ///
+    /// Shorthand for `row.get::<_, Id<D>>(column)`, handy when mapping rows to structs:
+    /// ```
+    /// use stable_identifier::*;
+    ///
+    /// struct Dog;
+    /// impl IdDomain for Dog {
+    ///     const NAME: &'static str = "Dog";
+    ///     type Backing = String;
+    ///     type Generator = ();
+    ///     type ConstRepr = ();
+    /// }
+    ///
+    /// struct DogRecord {
+    ///     id: Id<Dog>,
+    ///     name: String,
+    /// }
+    ///
+    /// impl DogRecord {
+    ///     fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+    ///         Ok(DogRecord {
+    ///             id: Id::from_column(row, "id")?,
+    ///             name: row.get("name")?,
+    ///         })
+    ///     }
+    /// }
+    /// ```
+    pub fn from_column(row: &Row<'_>, column: &str) -> rusqlite::Result<Self> {
+        row.get(column)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusqlite::Connection;
+
+    use super::*;
+
+    struct Dog;
+    impl IdDomain for Dog {
+        const NAME: &'static str = "Dog";
+        type Backing = i64;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    struct DogRecord {
+        id: Id<Dog>,
+        name: String,
+    }
+
+    impl DogRecord {
+        fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+            Ok(DogRecord {
+                id: Id::from_column(row, "id")?,
+                name: row.get("name")?,
+            })
+        }
+    }
+
+    #[test]
+    fn read_from_row() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE dog (id INTEGER PRIMARY KEY, name TEXT)", ())
+            .unwrap();
+        conn.execute(
+            "INSERT INTO dog (id, name) VALUES (?1, ?2)",
+            (Dog::new_id(7i64), "Rex"),
+        )
+        .unwrap();
+
+        let dog = conn
+            .query_row("SELECT id, name FROM dog", (), DogRecord::from_row)
+            .unwrap();
+        assert_eq!(dog.id, Dog::new_id(7i64));
+        assert_eq!(dog.name, "Rex");
+    }
+}
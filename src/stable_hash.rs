@@ -0,0 +1,285 @@
+use std::hash::{Hash, Hasher};
+
+use crate::{Id, IdDomain, crockford};
+
+/// A [`Hasher`] that always produces the same output for the same input, across runs, processes and platforms.
+///
+/// Uses 64-bit FNV-1a followed by the MurmurHash3 finalizer to spread the bits evenly. Integers are always
+/// hashed in little-endian byte order and `usize`/`isize` as 64-bit values, so results do not depend on the
+/// platform. The output does depend on how the hashed type implements [`Hash`] though.
+///
+/// This is not a cryptographic hash, and it's not resistant to hash flooding either; use it for
+/// things like sharding rather than as the hasher of a `HashMap` exposed to untrusted input.
+#[derive(Debug, Clone)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl StableHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Creates a hasher with the fixed initial state.
+    pub fn new() -> Self {
+        Self {
+            state: Self::OFFSET_BASIS,
+        }
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        let mut hash = self.state;
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        hash ^= hash >> 33;
+        hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        hash ^= hash >> 33;
+        hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: Hash,
+{
+    /// Hashes the backing value with [`StableHasher`], through [`IdDomain::hash_backing`].
+    ///
+    /// Unlike hashing with the randomly seeded hasher of a `HashMap`, the result is the same across
+    /// runs and processes, which makes it suitable for sharding, bloom filters and the like.
+    pub fn stable_hash(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        D::hash_backing(self.backing(), &mut hasher);
+        hasher.finish()
+    }
+
+    /// Assigns this identifier to one of `n` buckets (`0..n`), e.g. for picking a shard.
+    ///
+    /// The bucket is derived from [`Id::stable_hash`], so the same identifier is always
+    /// assigned to the same bucket, even across processes.
+    ///
+    /// Panics if `n` is zero.
+    pub fn shard(&self, n: u32) -> u32 {
+        assert!(n > 0, "cannot shard into zero buckets");
+        (self.stable_hash() % u64::from(n)) as u32
+    }
+
+    /// A short, [Crockford base32](crate::crockford) fingerprint of this identifier, for telling
+    /// identifiers apart at a glance in logs and dense tables.
+    ///
+    /// Always [`Id::FINGERPRINT_LEN`] characters, derived from [`Id::stable_hash`]. Different
+    /// identifiers can share a fingerprint, so never use it in place of the identifier itself.
+    pub fn fingerprint(&self) -> String {
+        let hash = self.stable_hash();
+        (0..Self::FINGERPRINT_LEN)
+            .map(|i| {
+                let shift = 64 - 5 * (i + 1);
+                crockford::ALPHABET[((hash >> shift) & 31) as usize] as char
+            })
+            .collect()
+    }
+}
+
+impl<D: IdDomain> Id<D> {
+    /// Number of characters in an [`Id::fingerprint`].
+    pub const FINGERPRINT_LEN: usize = 6;
+}
+
+/// Groups identifiers by their [`Id::shard`], e.g. to build one write batch per shard.
+///
+/// Returns `n` groups, where group `i` holds the identifiers assigned to shard `i` in their original order.
+///
+/// Panics if `n` is zero.
+pub fn partition_by_shard<D>(ids: impl IntoIterator<Item = Id<D>>, n: u32) -> Vec<Vec<Id<D>>>
+where
+    D: IdDomain,
+    D::Backing: Hash,
+{
+    assert!(n > 0, "cannot shard into zero buckets");
+    let mut shards: Vec<Vec<Id<D>>> = (0..n).map(|_| Vec::new()).collect();
+    for id in ids {
+        shards[id.shard(n) as usize].push(id);
+    }
+    shards
+}
+
+/// Derives a seed for domain `D` from a `master` seed, by hashing both with [`StableHasher`].
+///
+/// Seeding the generators of several domains from the same master seed gives each domain its own
+/// independent, but still reproducible, stream of identifiers.
+pub fn domain_seed<D: IdDomain>(master: u64) -> u64 {
+    let mut hasher = StableHasher::new();
+    master.hash(&mut hasher);
+    D::NAME.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Order;
+    impl IdDomain for Order {
+        const NAME: &'static str = "Order";
+        type Backing = u64;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn partition_across_shards() {
+        let ids: Vec<_> = (0..100u64).map(Order::new_id).collect();
+        let shards = partition_by_shard(ids.clone(), 4);
+        assert_eq!(shards.len(), 4);
+        assert_eq!(shards.iter().map(Vec::len).sum::<usize>(), 100);
+
+        for (index, shard) in shards.iter().enumerate() {
+            assert!(!shard.is_empty());
+            assert!(shard.iter().all(|id| id.shard(4) as usize == index));
+            assert!(shard.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+
+        let mut regrouped: Vec<_> = shards.into_iter().flatten().collect();
+        regrouped.sort();
+        assert_eq!(regrouped, ids);
+    }
+
+    #[test]
+    fn fingerprint_is_short_and_stable() {
+        let id = Order::new_id(12345u64);
+        let fingerprint = id.fingerprint();
+        assert_eq!(fingerprint.len(), Id::<Order>::FINGERPRINT_LEN);
+        assert_eq!(fingerprint, Order::new_id(12345u64).fingerprint());
+        assert_ne!(fingerprint, Order::new_id(12346u64).fingerprint());
+        assert!(
+            fingerprint
+                .bytes()
+                .all(|b| crockford::ALPHABET.contains(&b))
+        );
+        // Pinned, like the hash it is derived from.
+        assert_eq!(fingerprint, "NJ6659");
+    }
+
+    #[test]
+    fn stable_hash_is_deterministic() {
+        let id = Order::new_id(12345u64);
+        assert_eq!(id.stable_hash(), Order::new_id(12345u64).stable_hash());
+        assert_ne!(id.stable_hash(), Order::new_id(12346u64).stable_hash());
+
+        // Pinned, so that accidental changes to the algorithm are noticed.
+        let mut hasher = StableHasher::new();
+        hasher.write(b"stable");
+        assert_eq!(hasher.finish(), 0x613a_623f_9ef4_5c97);
+    }
+
+    #[test]
+    fn shard_is_stable() {
+        let id = Order::new_id(12345u64);
+        assert_eq!(id.shard(16), id.shard(16));
+        assert_eq!(id.shard(16), Order::new_id(12345u64).shard(16));
+        assert_eq!(id.shard(1), 0);
+    }
+
+    #[test]
+    fn shard_is_roughly_even() {
+        let mut buckets = [0u32; 8];
+        for value in 0..8000u64 {
+            buckets[Order::new_id(value).shard(8) as usize] += 1;
+        }
+        assert!(buckets.iter().all(|count| (800..1200).contains(count)));
+    }
+
+    fn hash_of(value: &impl Hash) -> u64 {
+        let mut hasher = StableHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // The expected values are pinned, so that these fail if enabling a feature (like `serde`)
+    // ever changes how identifiers hash, as the tests are run with and without features.
+    #[test]
+    fn hash_is_feature_independent() {
+        struct Customer;
+        impl IdDomain for Customer {
+            const NAME: &'static str = "Customer";
+            type Backing = String;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Customer::new_id("c-1");
+        assert_eq!(hash_of(&id), hash_of(id.backing()));
+        assert_eq!(hash_of(&id), 0xd331_0e04_31a8_4d81);
+    }
+
+    #[cfg(feature = "tiny_id")]
+    #[test]
+    fn tiny_id_hash_is_feature_independent() {
+        use crate::tiny_id::TinyId;
+
+        struct Customer;
+        impl IdDomain for Customer {
+            const NAME: &'static str = "Customer";
+            type Backing = TinyId<8>;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Customer::new_id("c-1");
+        assert_eq!(hash_of(&id), hash_of(id.backing()));
+        assert_eq!(hash_of(&id), 0xced6_4a7c_990f_6565);
+    }
+}
@@ -2,9 +2,44 @@
 //!
 //! Good enough for simple use cases, cheap and ergonomic to copy.
 //! Comes with a just-as-tiny ID generator, free of charge!
-use std::{convert::Infallible, str::FromStr};
+//!
+//! ## WebAssembly
+//!
+//! [`TinyIdGen`] draws its randomness from the operating system, which is not available on
+//! `wasm32-unknown-unknown` unless `getrandom` is built with its `js` feature. If you would rather not
+//! depend on that, use [`TinyIdRngGen`] with a random number generator you provide yourself,
+//! e.g. one seeded from `crypto.getRandomValues()` on the JavaScript side:
+//! ```
+//! use rand::{SeedableRng, rngs::StdRng};
+//! use stable_identifier::{*, tiny_id::*};
+//!
+//! struct Bird;
+//! impl IdDomain for Bird {
+//!     const NAME: &'static str = "Bird";
+//!     type Backing = TinyId;
+//!     type Generator = TinyIdRngGen<StdRng>;
+//!     type ConstRepr = ();
+//! }
+//!
+//! let seed_from_js = [7u8; 32];
+//! let mut generator = TinyIdRngGen::new(StdRng::from_seed(seed_from_js));
+//! let bird_id = Bird::generate_id_stateful(&mut generator);
+//! assert_eq!(bird_id.backing().len(), 21);
+//! ```
+use std::{
+    convert::Infallible,
+    marker::PhantomData,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-use crate::{GenerateIdStateless, Id, IdDomain};
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    BoundedDisplay, ConstPath, GenerateIdStateful, GenerateIdStateless, GenerateIdWith, Id,
+    IdDomain, crockford, domain_seed,
+};
 
 /// Constant-size backing type for string-based identifiers.
 /// The fixed size makes it allocation-free and cheap to copy.
@@ -12,23 +47,142 @@ use crate::{GenerateIdStateless, Id, IdDomain};
 /// It is expected that the bytes always make up a valid UTF8 string.
 ///
 /// The length of the identifier can be changed with the `N` associated constant
-/// and defaults to 21 bytes/characters.
+/// and defaults to 21 bytes/characters. `N` must be larger than zero, a `TinyId<0>`
+/// can never hold anything and is rejected at compile time:
+/// ```compile_fail
+/// use stable_identifier::tiny_id::TinyId;
+///
+/// let id = TinyId::<0>::from_bytes(b"nothing");
+/// ```
+/// This holds for every constructor, including conversions:
+/// ```compile_fail
+/// use stable_identifier::tiny_id::TinyId;
+///
+/// let id = TinyId::<0>::from([]);
+/// ```
+///
+/// If using serde, `TinyId` is stored as a string in human-readable formats and as exactly `N` bytes
+/// in binary formats, making its size predictable.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TinyId<const N: usize = 21> {
     text: [u8; N],
 }
 
 impl<const N: usize> TinyId<N> {
+    /// Referenced by every constructor, so that a `TinyId<0>` fails to compile wherever it is made.
+    const ASSERT_NON_EMPTY: () = assert!(N > 0, "TinyId must have a length larger than zero");
+
     /// Build ID from a byte slice, assumed to be valid utf-8.
     ///
     /// Will be trunctated to at most N bytes.
     /// If there are fewer bytes than the length of the array,
     /// the rest is set to `0u8` (null character).
+    ///
+    /// In debug builds, this panics if the bytes up to the first null character are not valid utf-8,
+    /// or if anything but null characters follows it.
     pub fn from_bytes(bytes: &[u8]) -> Self {
+        let () = Self::ASSERT_NON_EMPTY;
         let mut array = [0u8; N];
         let copy_len = bytes.len().min(N);
         array[..copy_len].copy_from_slice(&bytes[..copy_len]);
-        Self { text: array }
+        let id = Self { text: array };
+        id.debug_assert_utf8();
+        id
+    }
+
+    /// Build ID from a string in `const` contexts, e.g. for tables of ID constants.
+    ///
+    /// Unlike the other constructors, this panics if the string is longer than N bytes, which turns into a
+    /// compile error when evaluated at compile time:
+    /// ```compile_fail
+    /// use stable_identifier::tiny_id::TinyId;
+    ///
+    /// const TOO_LONG: TinyId<4> = TinyId::from_static("too long");
+    /// ```
+    pub const fn from_static(s: &'static str) -> Self {
+        let () = Self::ASSERT_NON_EMPTY;
+        let bytes = s.as_bytes();
+        assert!(bytes.len() <= N, "string does not fit in TinyId");
+        let mut text = [0u8; N];
+        let mut i = 0;
+        while i < bytes.len() {
+            text[i] = bytes[i];
+            i += 1;
+        }
+        Self { text }
+    }
+
+    /// Compares two IDs in `const` contexts, where `==` can't be used.
+    ///
+    /// ```
+    /// use stable_identifier::tiny_id::TinyId;
+    ///
+    /// const ADMIN: TinyId<8> = TinyId::from_static("admin");
+    /// const GUEST: TinyId<8> = TinyId::from_static("guest");
+    /// const ROLES: [TinyId<8>; 2] = [ADMIN, GUEST];
+    ///
+    /// const fn role_index(role: &TinyId<8>) -> Option<usize> {
+    ///     let mut i = 0;
+    ///     while i < ROLES.len() {
+    ///         if ROLES[i].const_eq(role) {
+    ///             return Some(i);
+    ///         }
+    ///         i += 1;
+    ///     }
+    ///     None
+    /// }
+    ///
+    /// const GUEST_INDEX: Option<usize> = role_index(&GUEST);
+    /// assert_eq!(GUEST_INDEX, Some(1));
+    /// ```
+    pub const fn const_eq(&self, other: &Self) -> bool {
+        let mut i = 0;
+        while i < N {
+            if self.text[i] != other.text[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    /// Catches invalid utf-8 where it is introduced, rather than when the ID is displayed.
+    fn debug_assert_utf8(&self) {
+        debug_assert!(
+            check_text(&self.text).is_ok(),
+            "TinyId must not be created from invalid utf-8 !"
+        );
+    }
+
+    /// Build ID from a string, truncating it to at most N bytes like [`TinyId::from_bytes`].
+    ///
+    /// Also returns the number of bytes that were dropped, so callers can tell whether the input fit.
+    /// The string is only ever cut at a character boundary, so the ID remains valid utf-8 even if
+    /// that means dropping a few more bytes.
+    pub fn from_str_checked(s: &str) -> (Self, usize) {
+        let mut keep = s.len().min(N);
+        while !s.is_char_boundary(keep) {
+            keep -= 1;
+        }
+        (Self::from_bytes(&s.as_bytes()[..keep]), s.len() - keep)
+    }
+
+    /// Build ID from a string, explicitly truncating it to at most N bytes at a character boundary.
+    ///
+    /// This is what `From<&str>` does, spelled out so lossy construction is easy to spot and search for.
+    pub fn from_str_truncating(s: &str) -> Self {
+        Self::from_str_checked(s).0
+    }
+
+    /// Build ID from a string, failing if it doesn't fit in N bytes instead of truncating it.
+    pub fn try_from_str(s: &str) -> Result<Self, CapacityError> {
+        match Self::from_str_checked(s) {
+            (id, 0) => Ok(id),
+            _ => Err(CapacityError {
+                capacity: N,
+                len: s.len(),
+            }),
+        }
     }
 
     /// Byte representation of this ID.
@@ -36,6 +190,11 @@ impl<const N: usize> TinyId<N> {
         &self.text
     }
 
+    /// Returns the inner array of bytes, including any null padding.
+    pub fn into_array(self) -> [u8; N] {
+        self.text
+    }
+
     /// String representation of this ID. Will panic if the internal bytes do not make up valid utf-8!
     pub fn as_str(&self) -> &str {
         std::str::from_utf8(self.as_bytes())
@@ -43,6 +202,26 @@ impl<const N: usize> TinyId<N> {
             .trim_end_matches('\0')
     }
 
+    /// Like [`TinyId::as_str`], but also trims trailing ASCII whitespace.
+    ///
+    /// Useful for IDs taken from fixed-width records that are padded with spaces rather than nulls.
+    pub fn as_str_trimmed(&self) -> &str {
+        self.as_str()
+            .trim_end_matches(|c: char| c.is_ascii_whitespace())
+    }
+
+    /// Checks whether the content of this ID, without the null padding, is exactly `pattern`.
+    ///
+    /// Saves building a `TinyId` just to compare against known bytes, e.g. when parsing a protocol.
+    pub fn matches_bytes(&self, pattern: &[u8]) -> bool {
+        &self.text[..self.len()] == pattern
+    }
+
+    /// The maximum length in bytes of this ID as a string, which is `N`. Same as [`BoundedDisplay::MAX_DISPLAY_LEN`].
+    pub const fn max_display_len() -> usize {
+        N
+    }
+
     /// Counts up until the first `0u8` (null character).
     pub fn len(&self) -> usize {
         self.text.iter().take_while(|byte| byte != &&0).count()
@@ -52,13 +231,152 @@ impl<const N: usize> TinyId<N> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Copies this ID into a `TinyId` of a different length `M`, e.g. when migrating a domain
+    /// to a longer or shorter identifier.
+    ///
+    /// Fails if the content of this ID does not fit in `M` bytes.
+    pub fn resize<const M: usize>(&self) -> Result<TinyId<M>, CapacityError> {
+        let len = self.len();
+        if len > M {
+            return Err(CapacityError { capacity: M, len });
+        }
+        Ok(TinyId::from_bytes(&self.text[..len]))
+    }
+}
+
+/// Why bytes can't be the content of a [`TinyId`], see [`check_text`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum InvalidText {
+    /// The bytes before the first null character are not valid utf-8.
+    Utf8(std::str::Utf8Error),
+    /// A byte other than the null character follows the first null character.
+    Padding,
+}
+
+/// Checks that `text` is what [`TinyId::as_str`] relies on: valid utf-8 up to the first null character,
+/// followed only by null characters.
+pub(crate) fn check_text(text: &[u8]) -> Result<(), InvalidText> {
+    let len = text
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(text.len());
+    std::str::from_utf8(&text[..len]).map_err(InvalidText::Utf8)?;
+    if text[len..].iter().any(|byte| *byte != 0) {
+        return Err(InvalidText::Padding);
+    }
+    Ok(())
+}
+
+/// A [`TinyId`] preceded in memory by a one-byte domain `TAG`, for telling domains apart in raw memory dumps.
+///
+/// The tag is purely forensic: it is not part of [`TaggedTinyId::as_str`], equality or hashing.
+/// The struct is `#[repr(C)]`, so the tag is always the byte right before the text.
+/// ```
+/// use stable_identifier::tiny_id::TaggedTinyId;
+///
+/// const ORDER_TAG: u8 = 0xA1;
+/// let id = TaggedTinyId::<ORDER_TAG, 8>::from("ord-1");
+/// assert_eq!(id.as_str(), "ord-1");
+/// assert_eq!(id.to_bytes()[0], ORDER_TAG);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct TaggedTinyId<const TAG: u8, const N: usize = 21> {
+    tag: u8,
+    id: TinyId<N>,
+}
+
+impl<const TAG: u8, const N: usize> TaggedTinyId<TAG, N> {
+    /// Tags an existing ID.
+    pub fn new(id: TinyId<N>) -> Self {
+        Self { tag: TAG, id }
+    }
+
+    /// The domain tag stored in front of the ID.
+    pub fn tag(&self) -> u8 {
+        self.tag
+    }
+
+    /// String representation of this ID, without the tag.
+    pub fn as_str(&self) -> &str {
+        self.id.as_str()
+    }
+
+    /// The bytes of this ID as laid out in memory: the tag, followed by the `N` bytes of the ID.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(N + 1);
+        bytes.push(self.tag);
+        bytes.extend_from_slice(self.id.as_bytes());
+        bytes
+    }
+
+    /// Returns the ID without its tag.
+    pub fn into_untagged(self) -> TinyId<N> {
+        self.id
+    }
+}
+
+impl<const TAG: u8, const N: usize> From<TinyId<N>> for TaggedTinyId<TAG, N> {
+    fn from(id: TinyId<N>) -> Self {
+        Self::new(id)
+    }
+}
+
+/// Truncates strings longer than N bytes, like [`TinyId::from_str_truncating`].
+impl<'a, const TAG: u8, const N: usize> From<&'a str> for TaggedTinyId<TAG, N> {
+    fn from(value: &'a str) -> Self {
+        Self::new(TinyId::from_str_truncating(value))
+    }
+}
+
+impl<const TAG: u8, const N: usize> PartialEq for TaggedTinyId<TAG, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
 }
 
+impl<const TAG: u8, const N: usize> Eq for TaggedTinyId<TAG, N> {}
+
+impl<const TAG: u8, const N: usize> std::hash::Hash for TaggedTinyId<TAG, N> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<const TAG: u8, const N: usize> std::fmt::Display for TaggedTinyId<TAG, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+/// Returned when some content does not fit in a [`TinyId`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The number of bytes available.
+    pub capacity: usize,
+    /// The number of bytes that were needed.
+    pub len: usize,
+}
+
+impl std::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} bytes do not fit in a TinyId of length {}",
+            self.len, self.capacity
+        )
+    }
+}
+
+impl std::error::Error for CapacityError {}
+
 impl<const N: usize> FromStr for TinyId<N> {
     type Err = Infallible;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        Ok(TinyId::from_bytes(s.as_bytes()))
+        let () = Self::ASSERT_NON_EMPTY;
+        Ok(TinyId::from_str_truncating(s))
     }
 }
 
@@ -68,9 +386,65 @@ impl<const N: usize> std::fmt::Display for TinyId<N> {
     }
 }
 
+/// Truncates strings longer than N bytes, like [`TinyId::from_str_truncating`].
+///
+/// This conversion is what lets `&'static str` act as [`IdDomain::ConstRepr`] and makes
+/// `new_id("...")` work, so it has to stay infallible. A `TryFrom<&str>` can't exist alongside it;
+/// use [`TinyId::try_from_str`] to reject input that doesn't fit.
 impl<'a, const N: usize> From<&'a str> for TinyId<N> {
     fn from(value: &'a str) -> Self {
-        TinyId::from_str(value).unwrap()
+        TinyId::from_str_truncating(value)
+    }
+}
+
+/// Joins a category and a name with [`ConstPath::SEPARATOR`], allowing a tuple as [`IdDomain::ConstRepr`].
+impl<'a, 'b, const N: usize> From<(&'a str, &'b str)> for TinyId<N> {
+    fn from((category, name): (&'a str, &'b str)) -> Self {
+        let separator = ConstPath::<2>::SEPARATOR;
+        TinyId::from_str_truncating(&format!("{category}{separator}{name}"))
+    }
+}
+
+impl<const N: usize, const M: usize> From<ConstPath<M>> for TinyId<N> {
+    fn from(path: ConstPath<M>) -> Self {
+        TinyId::from_str_truncating(&path.join())
+    }
+}
+
+/// Build ID from an array of bytes, assumed to be valid utf-8 and padded with `0u8` (null characters).
+///
+/// In debug builds, this panics if the bytes up to the first null character are not valid utf-8,
+/// or if anything but null characters follows it.
+impl<const N: usize> From<[u8; N]> for TinyId<N> {
+    fn from(text: [u8; N]) -> Self {
+        let () = Self::ASSERT_NON_EMPTY;
+        let id = Self { text };
+        id.debug_assert_utf8();
+        id
+    }
+}
+
+impl<const N: usize> PartialEq<str> for TinyId<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a, const N: usize> PartialEq<&'a str> for TinyId<N> {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> PartialEq<TinyId<N>> for str {
+    fn eq(&self, other: &TinyId<N>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<TinyId<N>> for &str {
+    fn eq(&self, other: &TinyId<N>) -> bool {
+        *self == other.as_str()
     }
 }
 
@@ -80,9 +454,15 @@ impl<const N: usize> AsRef<str> for TinyId<N> {
     }
 }
 
+// Human-readable formats (like JSON) store a `TinyId` as a string, while binary formats store it
+// as a tuple of exactly `N` bytes including the null padding.
 #[cfg(feature = "serde")]
 mod serde_impls {
-    use serde::{Deserialize, Serialize};
+    use serde::{
+        Deserialize, Serialize,
+        de::{Error, SeqAccess, Unexpected, Visitor},
+        ser::SerializeTuple,
+    };
 
     use super::*;
 
@@ -91,7 +471,15 @@ mod serde_impls {
         where
             S: serde::Serializer,
         {
-            serializer.serialize_str(self.as_str())
+            if serializer.is_human_readable() {
+                serializer.serialize_str(self.as_str())
+            } else {
+                let mut tuple = serializer.serialize_tuple(N)?;
+                for byte in &self.text {
+                    tuple.serialize_element(byte)?;
+                }
+                tuple.end()
+            }
         }
     }
 
@@ -100,90 +488,1233 @@ mod serde_impls {
         where
             D: serde::Deserializer<'de>,
         {
-            let s = String::deserialize(deserializer)?;
-            Ok(Self::from_str(&s).unwrap())
+            let () = Self::ASSERT_NON_EMPTY;
+            if deserializer.is_human_readable() {
+                let s = String::deserialize(deserializer)?;
+                Ok(Self::from_str(&s).unwrap())
+            } else {
+                deserializer.deserialize_tuple(N, BytesVisitor)
+            }
+        }
+    }
+
+    struct BytesVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+        type Value = TinyId<N>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "{N} bytes of utf-8 padded with null bytes")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut text = [0u8; N];
+            for (index, byte) in text.iter_mut().enumerate() {
+                *byte = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(index, &self))?;
+            }
+            check_text(&text).map_err(|_| Error::invalid_value(Unexpected::Bytes(&text), &self))?;
+            Ok(TinyId::from(text))
         }
     }
 }
 
-/// Allows generating [`TinyId`] identifiers using the [`nanoid`](https://docs.rs/nanoid) crate.
-/// The generated string length is configurable with `N` and defaults to 21 characters.
-pub struct TinyIdGen<const N: usize = 21>;
-impl<const N: usize, D> GenerateIdStateless<D> for TinyIdGen<N>
-where
-    D: IdDomain<Backing = TinyId<N>>,
-{
-    fn generate_id() -> Id<D> {
-        Id::new(TinyId::from_str(&nanoid::nanoid!(N)).unwrap())
+/// Wrapper that compares and hashes a string-like ID without regard to ASCII case, while
+/// keeping the original casing around.
+///
+/// Useful as a map key for things like usernames: `"Alice"` can be found by looking up `"alice"`,
+/// while [`CaseInsensitive::as_str`] still returns `"Alice"`. Only ASCII letters are folded.
+#[derive(Debug, Clone, Copy)]
+pub struct CaseInsensitive<T>(pub T);
+
+impl<T: AsRef<str>> CaseInsensitive<T> {
+    /// The wrapped ID as a string, in its original casing.
+    pub fn as_str(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    /// Returns the wrapped ID.
+    pub fn into_inner(self) -> T {
+        self.0
     }
 }
 
-#[macro_export]
-/// Convenience macro to declare a type as being an identifier domain using TinyId as a backing type.
-macro_rules! tiny_id_domain {
-    // Standard length
-    ($domain_type:ident, $name:literal) => {
-        impl $crate::IdDomain for $domain_type {
-            const NAME: &'static str = $name;
-            type Backing = $crate::tiny_id::TinyId;
-            type Generator = $crate::tiny_id::TinyIdGen;
-            type ConstRepr = &'static str;
+impl<T: AsRef<str>> PartialEq for CaseInsensitive<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str().eq_ignore_ascii_case(other.as_str())
+    }
+}
+
+impl<T: AsRef<str>> Eq for CaseInsensitive<T> {}
+
+impl<T: AsRef<str>> std::hash::Hash for CaseInsensitive<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for byte in self.as_str().bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
         }
-    };
+        // Same terminator as `str`, so that sequences of strings hash unambiguously.
+        state.write_u8(0xff);
+    }
+}
 
-    // Custom length
-    ($domain_type:ident, $name:literal, $length:literal) => {
-        impl $crate::IdDomain for $domain_type {
-            const NAME: &'static str = $name;
-            type Backing = $crate::tiny_id::TinyId<$length>;
-            type Generator = $crate::tiny_id::TinyIdGen<$length>;
-            type ConstRepr = &'static str;
+/// Constant-size backing type for binary identifiers, like raw 16-byte keys.
+///
+/// Unlike [`TinyId`], the bytes are not assumed to be text: every byte is significant, including null bytes.
+/// Displays as lowercase hexadecimal, which is also what [`FromStr`] parses.
+///
+/// If using serde, `BinId` is stored as a hexadecimal string in human-readable formats and as bytes
+/// in binary formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BinId<const N: usize = 16> {
+    bytes: [u8; N],
+}
+
+impl<const N: usize> BinId<N> {
+    /// Build ID from an array of bytes.
+    pub const fn new(bytes: [u8; N]) -> Self {
+        Self { bytes }
+    }
+
+    /// Byte representation of this ID.
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.bytes
+    }
+
+    /// Returns the inner array of bytes.
+    pub fn into_array(self) -> [u8; N] {
+        self.bytes
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for BinId<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for BinId<N> {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl<const N: usize> BoundedDisplay for TinyId<N> {
+    const MAX_DISPLAY_LEN: usize = N;
+}
+
+impl<const N: usize> BoundedDisplay for BinId<N> {
+    /// Two hexadecimal digits per byte.
+    const MAX_DISPLAY_LEN: usize = N * 2;
+}
+
+impl<const N: usize> std::fmt::Display for BinId<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in &self.bytes {
+            write!(f, "{byte:02x}")?;
         }
-    };
+        Ok(())
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    pub use super::*;
+impl<const N: usize> FromStr for BinId<N> {
+    type Err = ParseBinIdError;
 
-    #[test]
-    fn has_correct_length() {
-        struct Bird;
-        impl IdDomain for Bird {
-            const NAME: &'static str = "Bird";
-            type Backing = TinyId<16>;
-            type Generator = TinyIdGen<16>;
-            type ConstRepr = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != N * 2 || !s.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(ParseBinIdError);
+        }
+        let mut bytes = [0u8; N];
+        for (byte, pair) in bytes.iter_mut().zip(s.as_bytes().chunks(2)) {
+            let pair = std::str::from_utf8(pair).map_err(|_| ParseBinIdError)?;
+            *byte = u8::from_str_radix(pair, 16).map_err(|_| ParseBinIdError)?;
         }
+        Ok(Self::new(bytes))
+    }
+}
 
-        let new_bird_id = Bird::generate_id();
-        assert_eq!(new_bird_id.backing().len(), 16);
+/// Returned when a string is not a hexadecimal [`BinId`] of the expected length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseBinIdError;
+
+impl std::fmt::Display for ParseBinIdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid hexadecimal BinId")
     }
+}
 
-    #[test]
-    fn len_works() {
-        struct Bird;
-        impl IdDomain for Bird {
-            const NAME: &'static str = "Bird";
-            type Backing = TinyId<16>;
-            type Generator = ();
-            type ConstRepr = ();
+impl std::error::Error for ParseBinIdError {}
+
+#[cfg(feature = "serde")]
+mod bin_serde_impls {
+    use serde::{Deserialize, Serialize, de::Visitor};
+
+    use super::*;
+
+    impl<const N: usize> Serialize for BinId<N> {
+        fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            if serializer.is_human_readable() {
+                serializer.collect_str(self)
+            } else {
+                serializer.serialize_bytes(&self.bytes)
+            }
         }
+    }
 
-        let new_bird_id = Bird::new_id("short");
-        assert_eq!(new_bird_id.backing().len(), 5);
+    impl<'de, const N: usize> Deserialize<'de> for BinId<N> {
+        fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            if deserializer.is_human_readable() {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            } else {
+                deserializer.deserialize_bytes(BytesVisitor)
+            }
+        }
     }
 
-    #[test]
-    fn macro_works() {
-        struct Bird;
-        tiny_id_domain!(Bird, "Bird");
+    struct BytesVisitor<const N: usize>;
 
-        let new_bird_id = Bird::generate_id();
-        assert_eq!(new_bird_id.backing().len(), 21);
+    impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+        type Value = BinId<N>;
 
-        const CONST_BIRD: &str = "constant";
-        let unconst_bird = Bird::new_id(CONST_BIRD);
-        assert_eq!(unconst_bird.backing().as_str(), "constant");
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(formatter, "{N} bytes")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            let bytes = v
+                .try_into()
+                .map_err(|_| E::invalid_length(v.len(), &self))?;
+            Ok(BinId::new(bytes))
+        }
+    }
+}
+
+/// A set of characters that generated identifiers are made of, chosen at compile time.
+///
+/// The characters must all be ASCII, so that each generated character takes exactly one byte of the
+/// [`TinyId`]. The generators check this at compile time:
+/// ```compile_fail
+/// use stable_identifier::{*, tiny_id::*};
+///
+/// struct Greek;
+/// impl Alphabet for Greek {
+///     const CHARS: &'static [char] = &['α', 'β', 'γ'];
+/// }
+///
+/// struct Letter;
+/// impl IdDomain for Letter {
+///     const NAME: &'static str = "Letter";
+///     type Backing = TinyId<8>;
+///     type Generator = TinyIdGen<8, Greek>;
+///     type ConstRepr = ();
+/// }
+///
+/// let letter = Letter::generate_id();
+/// ```
+pub trait Alphabet {
+    /// The characters to pick from when generating identifiers, all of them ASCII.
+    const CHARS: &'static [char];
+}
+
+/// Fails to compile for an alphabet with non-ASCII characters, which would take more than one byte each.
+fn assert_ascii<A: Alphabet>() {
+    const {
+        let mut i = 0;
+        while i < A::CHARS.len() {
+            assert!(
+                A::CHARS[i].is_ascii(),
+                "Alphabet must only contain ASCII characters"
+            );
+            i += 1;
+        }
+    };
+}
+
+/// The default alphabet of [`nanoid`](https://docs.rs/nanoid): `A-Z`, `a-z`, `0-9`, `_` and `-`.
+pub struct UrlSafe;
+impl Alphabet for UrlSafe {
+    const CHARS: &'static [char] = &nanoid::alphabet::SAFE;
+}
+
+/// Lowercase ASCII letters `a-z`.
+pub struct Lowercase;
+impl Alphabet for Lowercase {
+    const CHARS: &'static [char] = &[
+        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r',
+        's', 't', 'u', 'v', 'w', 'x', 'y', 'z',
+    ];
+}
+
+/// ASCII digits `0-9`.
+pub struct Digits;
+impl Alphabet for Digits {
+    const CHARS: &'static [char] = &['0', '1', '2', '3', '4', '5', '6', '7', '8', '9'];
+}
+
+/// Allows generating [`TinyId`] identifiers using the [`nanoid`](https://docs.rs/nanoid) crate.
+/// The generated string length is configurable with `N` and defaults to 21 characters.
+///
+/// The characters used are determined by the [`Alphabet`] `A`, and default to [`UrlSafe`].
+pub struct TinyIdGen<const N: usize = 21, A = UrlSafe> {
+    _alphabet: PhantomData<A>,
+}
+
+impl<const N: usize, A: Alphabet> TinyIdGen<N, A> {
+    /// Approximate number of identifiers this generator makes before a 1% chance of a collision,
+    /// see [`collision_estimate`](crate::collision_estimate).
+    pub const COLLISION_ESTIMATE: u128 = crate::collision_estimate(A::CHARS.len(), N);
+}
+
+impl<const N: usize, A, D> GenerateIdStateless<D> for TinyIdGen<N, A>
+where
+    A: Alphabet,
+    D: IdDomain<Backing = TinyId<N>>,
+{
+    fn generate_id() -> Id<D> {
+        assert_ascii::<A>();
+        Id::new(TinyId::from_str(&nanoid::nanoid!(N, A::CHARS)).unwrap())
+    }
+}
+
+/// Option for [`TinyIdGen`] to generate an identifier of a specific number of characters, see
+/// [`IdDomain::generate_id_with`].
+///
+/// Lengths larger than the capacity `N` of the [`TinyId`] are clamped to `N`, and a length of zero
+/// makes an empty identifier.
+/// ```
+/// use stable_identifier::{*, tiny_id::*};
+///
+/// struct Invite;
+/// impl IdDomain for Invite {
+///     const NAME: &'static str = "Invite";
+///     type Backing = TinyId<21>;
+///     type Generator = TinyIdGen<21>;
+///     type ConstRepr = ();
+/// }
+///
+/// let short = Invite::generate_id_with(IdLength(8));
+/// assert_eq!(short.backing().len(), 8);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdLength(pub usize);
+
+impl<const N: usize, A, D> GenerateIdWith<D, IdLength> for TinyIdGen<N, A>
+where
+    A: Alphabet,
+    D: IdDomain<Backing = TinyId<N>>,
+{
+    fn generate_id_with(IdLength(len): IdLength) -> Id<D> {
+        assert_ascii::<A>();
+        let len = len.min(N);
+        if len == 0 {
+            return Id::new(TinyId::from_bytes(&[]));
+        }
+        Id::new(TinyId::from_str(&nanoid::nanoid!(len, A::CHARS)).unwrap())
+    }
+}
+
+/// Generates identifiers of `LEN` characters into a [`TinyId<N>`], leaving room to spare when `LEN < N`.
+///
+/// Unlike [`TinyIdGen`], the capacity of the backing and the generated length are independent,
+/// e.g. for generating short identifiers in a domain that also holds longer, imported ones.
+/// `LEN` can't be larger than `N`, which is checked at compile time:
+/// ```compile_fail
+/// use stable_identifier::{*, tiny_id::*};
+///
+/// struct Coupon;
+/// impl IdDomain for Coupon {
+///     const NAME: &'static str = "Coupon";
+///     type Backing = TinyId<8>;
+///     type Generator = TinyIdGenLen<8, 12>;
+///     type ConstRepr = ();
+/// }
+///
+/// let coupon = Coupon::generate_id();
+/// ```
+/// `LEN` must also be larger than zero:
+/// ```compile_fail
+/// use stable_identifier::{*, tiny_id::*};
+///
+/// struct Coupon;
+/// impl IdDomain for Coupon {
+///     const NAME: &'static str = "Coupon";
+///     type Backing = TinyId<8>;
+///     type Generator = TinyIdGenLen<8, 0>;
+///     type ConstRepr = ();
+/// }
+///
+/// let coupon = Coupon::generate_id();
+/// ```
+pub struct TinyIdGenLen<const N: usize, const LEN: usize, A = UrlSafe> {
+    _alphabet: PhantomData<A>,
+}
+
+impl<const N: usize, const LEN: usize, A: Alphabet> TinyIdGenLen<N, LEN, A> {
+    /// Same as [`TinyIdGen::COLLISION_ESTIMATE`], for identifiers of length `LEN`.
+    pub const COLLISION_ESTIMATE: u128 = crate::collision_estimate(A::CHARS.len(), LEN);
+}
+
+impl<const N: usize, const LEN: usize, A, D> GenerateIdStateless<D> for TinyIdGenLen<N, LEN, A>
+where
+    A: Alphabet,
+    D: IdDomain<Backing = TinyId<N>>,
+{
+    fn generate_id() -> Id<D> {
+        const {
+            assert!(
+                LEN <= N,
+                "TinyIdGenLen can't generate more characters than fit in the TinyId"
+            );
+            assert!(LEN > 0, "TinyIdGenLen must generate at least one character");
+        };
+        assert_ascii::<A>();
+        Id::new(TinyId::from_str(&nanoid::nanoid!(LEN, A::CHARS)).unwrap())
+    }
+}
+
+/// Generates [`TinyId`] identifiers like [`TinyIdGen`], using a random number generator
+/// provided by you instead of the operating system.
+///
+/// Useful on platforms without a default source of randomness (see the [module docs](self)),
+/// or for reproducible identifiers when using a seeded generator.
+pub struct TinyIdRngGen<R, const N: usize = 21, A = UrlSafe> {
+    rng: R,
+    _alphabet: PhantomData<A>,
+}
+
+impl<R, const N: usize, A: Alphabet> TinyIdRngGen<R, N, A> {
+    /// Same as [`TinyIdGen::COLLISION_ESTIMATE`].
+    pub const COLLISION_ESTIMATE: u128 = TinyIdGen::<N, A>::COLLISION_ESTIMATE;
+}
+
+impl<R: RngCore, const N: usize, A> TinyIdRngGen<R, N, A> {
+    /// Creates a generator drawing randomness from `rng`.
+    pub fn new(rng: R) -> Self {
+        Self {
+            rng,
+            _alphabet: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped random number generator.
+    pub fn into_rng(self) -> R {
+        self.rng
+    }
+}
+
+/// A [`TinyIdRngGen`] using a seeded [`ChaCha8Rng`](https://docs.rs/rand_chacha), for reproducible identifiers.
+///
+/// Unlike `StdRng`, whose algorithm may change between `rand` releases, ChaCha8 is a specified algorithm,
+/// so the same seed keeps producing the same identifiers across dependency updates.
+pub type SeededGen<const N: usize = 21, A = UrlSafe> = TinyIdRngGen<ChaCha8Rng, N, A>;
+
+impl<const N: usize, A> SeededGen<N, A> {
+    /// Creates a generator for domain `D`, seeded from `master` combined with the domain name
+    /// (see [`domain_seed`]).
+    ///
+    /// Domains sharing a master seed get independent streams, instead of correlated identifiers
+    /// from reusing the same seed.
+    pub fn for_domain<D: IdDomain>(master: u64) -> Self {
+        Self::new(ChaCha8Rng::seed_from_u64(domain_seed::<D>(master)))
+    }
+}
+
+impl<R, const N: usize, A, D> GenerateIdStateful<D> for TinyIdRngGen<R, N, A>
+where
+    R: RngCore,
+    A: Alphabet,
+    D: IdDomain<Backing = TinyId<N>>,
+{
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        assert_ascii::<A>();
+        let text: String = (0..N)
+            .map(|_| A::CHARS[self.rng.gen_range(0..A::CHARS.len())])
+            .collect();
+        Id::new(TinyId::from_str(&text).unwrap())
+    }
+}
+
+/// How precisely [`TimePrefixedGen`] records the time an identifier was generated at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimestampResolution {
+    /// Milliseconds since the Unix epoch, taking 10 characters like the timestamp of a ULID.
+    #[default]
+    Millis,
+    /// Seconds since the Unix epoch, taking 8 characters.
+    Seconds,
+}
+
+impl TimestampResolution {
+    /// Number of characters the timestamp takes at the start of an identifier.
+    pub const fn prefix_len(self) -> usize {
+        match self {
+            TimestampResolution::Millis => 10,
+            TimestampResolution::Seconds => 8,
+        }
+    }
+}
+
+/// Generates [`TinyId`] identifiers that start with the time they were generated at, followed by random
+/// characters from the [`Alphabet`] `A`, like a ULID packed into a `TinyId`.
+///
+/// The timestamp is written in [Crockford base32](crate::crockford) with a fixed width, so identifiers
+/// sort by the time they were generated at, up to the chosen [`TimestampResolution`]. Identifiers generated
+/// within the same millisecond or second share their prefix, and sort randomly among each other.
+///
+/// The resolution trades ordering against entropy: [`TimestampResolution::Seconds`] leaves two more
+/// characters for randomness, but orders identifiers less precisely, and every identifier generated within
+/// one second competes for the same random space. Check that the random part, `N` minus
+/// [`TimestampResolution::prefix_len`] characters, is long enough for the number of identifiers generated
+/// per window, e.g. with [`collision_estimate`](crate::collision_estimate). `N` must leave room for at
+/// least one random character, [`TimePrefixedGen::new`] panics otherwise.
+///
+/// ```
+/// use stable_identifier::{*, tiny_id::*};
+///
+/// struct Event;
+/// impl IdDomain for Event {
+///     const NAME: &'static str = "Event";
+///     type Backing = TinyId<16>;
+///     type Generator = TimePrefixedGen<16>;
+///     type ConstRepr = ();
+/// }
+///
+/// let mut generator = TimePrefixedGen::new(TimestampResolution::Seconds);
+/// let id = Event::generate_id_stateful(&mut generator);
+/// assert_eq!(id.backing().len(), 16);
+/// ```
+pub struct TimePrefixedGen<const N: usize = 21, A = UrlSafe> {
+    resolution: TimestampResolution,
+    _alphabet: PhantomData<A>,
+}
+
+impl<const N: usize, A: Alphabet> TimePrefixedGen<N, A> {
+    /// Creates a generator recording time with the given resolution.
+    ///
+    /// Panics if `N` is not larger than [`TimestampResolution::prefix_len`], which would leave no room
+    /// for random characters.
+    pub fn new(resolution: TimestampResolution) -> Self {
+        assert!(
+            N > resolution.prefix_len(),
+            "TimePrefixedGen<{N}> leaves no room for random characters after the timestamp"
+        );
+        Self {
+            resolution,
+            _alphabet: PhantomData,
+        }
+    }
+
+    /// The resolution of the timestamp at the start of generated identifiers.
+    pub fn resolution(&self) -> TimestampResolution {
+        self.resolution
+    }
+
+    /// Generates an identifier as if the current time was `time`. Times before the Unix epoch are
+    /// recorded as the epoch itself.
+    pub fn generate_at<D>(&self, time: SystemTime) -> Id<D>
+    where
+        D: IdDomain<Backing = TinyId<N>>,
+    {
+        assert_ascii::<A>();
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let timestamp = match self.resolution {
+            TimestampResolution::Millis => {
+                crockford::encode(&(since_epoch.as_millis() as u64).to_be_bytes()[2..])
+            }
+            TimestampResolution::Seconds => {
+                crockford::encode(&since_epoch.as_secs().to_be_bytes()[3..])
+            }
+        };
+        let random_len = N.saturating_sub(timestamp.len());
+        let random = nanoid::nanoid!(random_len, A::CHARS);
+        Id::new(TinyId::from_str_truncating(&(timestamp + &random)))
+    }
+}
+
+impl<const N: usize, A: Alphabet> Default for TimePrefixedGen<N, A> {
+    fn default() -> Self {
+        Self::new(TimestampResolution::default())
+    }
+}
+
+impl<const N: usize, A, D> GenerateIdStateful<D> for TimePrefixedGen<N, A>
+where
+    A: Alphabet,
+    D: IdDomain<Backing = TinyId<N>>,
+{
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        self.generate_at(SystemTime::now())
+    }
+}
+
+#[macro_export]
+/// Convenience macro to declare a type as being an identifier domain using TinyId as a backing type.
+macro_rules! tiny_id_domain {
+    // Standard length
+    ($domain_type:ident, $name:literal) => {
+        impl $crate::IdDomain for $domain_type {
+            const NAME: &'static str = $name;
+            type Backing = $crate::tiny_id::TinyId;
+            type Generator = $crate::tiny_id::TinyIdGen;
+            type ConstRepr = &'static str;
+        }
+    };
+
+    // Custom length
+    ($domain_type:ident, $name:literal, $length:literal) => {
+        impl $crate::IdDomain for $domain_type {
+            const NAME: &'static str = $name;
+            type Backing = $crate::tiny_id::TinyId<$length>;
+            type Generator = $crate::tiny_id::TinyIdGen<$length>;
+            type ConstRepr = &'static str;
+        }
+    };
+}
+
+/// Conversions to and from [`heapless::String`](https://docs.rs/heapless) of the same capacity, which never truncate.
+#[cfg(feature = "heapless")]
+mod heapless_string {
+    use super::TinyId;
+
+    impl<const N: usize> From<heapless::String<N>> for TinyId<N> {
+        fn from(value: heapless::String<N>) -> Self {
+            let () = TinyId::<N>::ASSERT_NON_EMPTY;
+            TinyId::from_str_truncating(&value)
+        }
+    }
+
+    impl<const N: usize> From<TinyId<N>> for heapless::String<N> {
+        fn from(value: TinyId<N>) -> Self {
+            let mut string = heapless::String::new();
+            string
+                .push_str(value.as_str())
+                .expect("TinyId<N> never holds more than N bytes");
+            string
+        }
+    }
+}
+
+#[cfg(feature = "secrecy")]
+impl<const N: usize> secrecy::Zeroize for TinyId<N> {
+    fn zeroize(&mut self) {
+        secrecy::Zeroize::zeroize(&mut self.text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    pub use super::*;
+
+    #[test]
+    fn has_correct_length() {
+        struct Bird;
+        impl IdDomain for Bird {
+            const NAME: &'static str = "Bird";
+            type Backing = TinyId<16>;
+            type Generator = TinyIdGen<16>;
+            type ConstRepr = ();
+        }
+
+        let new_bird_id = Bird::generate_id();
+        assert_eq!(new_bird_id.backing().len(), 16);
+    }
+
+    #[test]
+    fn generated_length_independent_of_capacity() {
+        struct Coupon;
+        impl IdDomain for Coupon {
+            const NAME: &'static str = "Coupon";
+            type Backing = TinyId<21>;
+            type Generator = TinyIdGenLen<21, 12>;
+            type ConstRepr = ();
+        }
+
+        let coupon = Coupon::generate_id();
+        assert_eq!(coupon.backing().len(), 12);
+        assert_eq!(coupon.backing().as_bytes()[12..], [0; 9]);
+
+        struct Pin;
+        impl IdDomain for Pin {
+            const NAME: &'static str = "Pin";
+            type Backing = TinyId<4>;
+            type Generator = TinyIdGenLen<4, 4, Digits>;
+            type ConstRepr = ();
+        }
+        assert!(
+            Pin::generate_id()
+                .backing()
+                .as_str()
+                .chars()
+                .all(|c| c.is_ascii_digit())
+        );
+    }
+
+    #[test]
+    fn length_per_call() {
+        struct Invite;
+        impl IdDomain for Invite {
+            const NAME: &'static str = "Invite";
+            type Backing = TinyId<16>;
+            type Generator = TinyIdGen<16>;
+            type ConstRepr = ();
+        }
+
+        assert_eq!(Invite::generate_id_with(IdLength(6)).backing().len(), 6);
+        assert_eq!(Invite::generate_id_with(IdLength(12)).backing().len(), 12);
+        assert_eq!(Invite::generate_id_with(IdLength(40)).backing().len(), 16);
+        assert!(Invite::generate_id_with(IdLength(0)).backing().is_empty());
+    }
+
+    #[test]
+    fn len_works() {
+        struct Bird;
+        impl IdDomain for Bird {
+            const NAME: &'static str = "Bird";
+            type Backing = TinyId<16>;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let new_bird_id = Bird::new_id("short");
+        assert_eq!(new_bird_id.backing().len(), 5);
+    }
+
+    #[test]
+    fn checked_reports_dropped() {
+        let (id, dropped) = TinyId::<8>::from_str_checked("fits");
+        assert_eq!(id.as_str(), "fits");
+        assert_eq!(dropped, 0);
+
+        let (id, dropped) = TinyId::<8>::from_str_checked("much too long");
+        assert_eq!(id.as_str(), "much too");
+        assert_eq!(dropped, 5);
+
+        // "ø" takes up two bytes and would be split at the limit.
+        let (id, dropped) = TinyId::<8>::from_str_checked("abcdefgø");
+        assert_eq!(id.as_str(), "abcdefg");
+        assert_eq!(dropped, 2);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid utf-8")]
+    fn invalid_utf8_bytes() {
+        TinyId::<4>::from_bytes(&[b'a', 0xff, 0xfe]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid utf-8")]
+    fn invalid_utf8_array() {
+        let _ = TinyId::from([b'a', 0xc3, 0, 0]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "invalid utf-8")]
+    fn invalid_utf8_after_padding() {
+        let _ = TinyId::from([b'a', 0, 0xff]);
+    }
+
+    #[test]
+    fn check_text_covers_padding() {
+        assert_eq!(check_text(b"ab\0\0"), Ok(()));
+        assert_eq!(check_text(b"abcd"), Ok(()));
+        assert!(matches!(check_text(&[0xff, 0]), Err(InvalidText::Utf8(_))));
+        assert_eq!(check_text(b"a\0b"), Err(InvalidText::Padding));
+    }
+
+    #[test]
+    fn from_str_keeps_utf8() {
+        let id = TinyId::<8>::from("abcdefgø");
+        assert_eq!(id.as_str(), "abcdefg");
+    }
+
+    #[test]
+    fn case_insensitive_keys() {
+        use std::collections::HashMap;
+
+        let mut users = HashMap::new();
+        users.insert(CaseInsensitive(TinyId::<16>::from("Alice")), 1);
+
+        let lookup = CaseInsensitive(TinyId::<16>::from("alice"));
+        assert_eq!(users.get(&lookup), Some(&1));
+        assert_eq!(users.get(&CaseInsensitive(TinyId::from("bob"))), None);
+
+        let keys: Vec<_> = users.keys().map(|key| key.as_str()).collect();
+        assert_eq!(keys, ["Alice"]);
+    }
+
+    #[test]
+    fn nanoid_collision_estimate() {
+        // The nanoid collision calculator gives ~149 billion years at 1000 IDs per hour.
+        assert_eq!(<TinyIdGen>::COLLISION_ESTIMATE, 1_307_638_670_955_289_460);
+        assert_eq!(
+            TinyIdRngGen::<rand::rngs::StdRng, 6, Digits>::COLLISION_ESTIMATE,
+            141
+        );
+    }
+
+    #[test]
+    fn tag_in_bytes_not_in_str() {
+        struct Order;
+        impl IdDomain for Order {
+            const NAME: &'static str = "Order";
+            type Backing = TaggedTinyId<0xA1, 8>;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        let id = Order::new_id("ord-1");
+        assert_eq!(id.backing().tag(), 0xA1);
+        assert_eq!(id.backing().to_bytes(), b"\xA1ord-1\0\0\0");
+        assert_eq!(id.backing().as_str(), "ord-1");
+        assert_eq!(id.to_string(), "Order [ord-1]");
+        assert_eq!(
+            id.into_backing().into_untagged(),
+            TinyId::<8>::from("ord-1")
+        );
+        assert_eq!(std::mem::size_of::<TaggedTinyId<0xA1, 8>>(), 9);
+    }
+
+    #[test]
+    fn const_lookup_table() {
+        const SAW: TinyId<8> = TinyId::from_static("saw");
+        const AXE: TinyId<8> = TinyId::from_static("axe");
+        const TOOLS: [(TinyId<8>, u32); 2] = [(SAW, 10), (AXE, 20)];
+
+        const fn price(tool: &TinyId<8>) -> Option<u32> {
+            let mut i = 0;
+            while i < TOOLS.len() {
+                if TOOLS[i].0.const_eq(tool) {
+                    return Some(TOOLS[i].1);
+                }
+                i += 1;
+            }
+            None
+        }
+
+        const AXE_PRICE: Option<u32> = price(&AXE);
+        const KNIFE_PRICE: Option<u32> = price(&TinyId::from_static("knife"));
+        assert_eq!(AXE_PRICE, Some(20));
+        assert_eq!(KNIFE_PRICE, None);
+        assert_eq!(SAW, TinyId::<8>::from("saw"));
+        assert!(!SAW.const_eq(&AXE));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_round_trip() {
+        let id = TinyId::<8>::from("grüße");
+        let string: heapless::String<8> = id.into();
+        assert_eq!(string.as_str(), "grüße");
+        assert_eq!(TinyId::from(string), id);
+
+        let full = heapless::String::<4>::try_from("full").unwrap();
+        assert_eq!(heapless::String::from(TinyId::from(full.clone())), full);
+    }
+
+    #[test]
+    fn explicit_truncation() {
+        let id = TinyId::<4>::from_str_truncating("abcdef");
+        assert_eq!(id, "abcd");
+        assert_eq!(TinyId::<4>::from("abcdef"), id);
+
+        assert_eq!(TinyId::<4>::try_from_str("abcd"), Ok(id));
+        assert_eq!(
+            TinyId::<4>::try_from_str("abcdef"),
+            Err(CapacityError {
+                capacity: 4,
+                len: 6
+            })
+        );
+    }
+
+    #[test]
+    fn compare_with_str() {
+        let id = TinyId::<8>::from("admin");
+        assert!(id == "admin");
+        assert!("admin" == id);
+        assert!(id == *"admin");
+        assert!(*"admin" == id);
+        assert!(id != "guest");
+        assert!("guest" != id);
+    }
+
+    #[test]
+    fn display_bound() {
+        const LEN: usize = TinyId::<16>::max_display_len();
+        assert_eq!(LEN, 16);
+        assert_eq!(<TinyId<16> as BoundedDisplay>::MAX_DISPLAY_LEN, 16);
+        assert_eq!(<BinId<4> as BoundedDisplay>::MAX_DISPLAY_LEN, 8);
+
+        struct Bird;
+        impl IdDomain for Bird {
+            const NAME: &'static str = "Bird";
+            type Backing = TinyId<16>;
+            type Generator = TinyIdGen<16>;
+            type ConstRepr = ();
+        }
+
+        let mut buffer = [0u8; Id::<Bird>::max_display_len()];
+        let id = Bird::generate_id();
+        let mut cursor = &mut buffer[..];
+        std::io::Write::write_fmt(&mut cursor, format_args!("{id}")).unwrap();
+        assert!(cursor.is_empty());
+    }
+
+    #[test]
+    fn time_prefix_shared_within_window() {
+        use std::time::Duration;
+
+        struct Event;
+        impl IdDomain for Event {
+            const NAME: &'static str = "Event";
+            type Backing = TinyId<21>;
+            type Generator = TimePrefixedGen<21>;
+            type ConstRepr = ();
+        }
+
+        let start = UNIX_EPOCH + Duration::from_millis(1_700_000_000_000);
+        let later = start + Duration::from_millis(250);
+        let next_second = start + Duration::from_secs(1);
+
+        let seconds = TimePrefixedGen::<21>::new(TimestampResolution::Seconds);
+        let first: Id<Event> = seconds.generate_at(start);
+        let second: Id<Event> = seconds.generate_at(later);
+        let third: Id<Event> = seconds.generate_at(next_second);
+        assert_eq!(
+            first.backing().as_str()[..8],
+            second.backing().as_str()[..8]
+        );
+        assert_ne!(first, second);
+        assert!(second.backing().as_str()[..8] < third.backing().as_str()[..8]);
+        assert_eq!(first.backing().len(), 21);
+
+        let millis = TimePrefixedGen::<21>::default();
+        let first: Id<Event> = millis.generate_at(start);
+        let second: Id<Event> = millis.generate_at(start);
+        let third: Id<Event> = millis.generate_at(later);
+        assert_eq!(
+            first.backing().as_str()[..10],
+            second.backing().as_str()[..10]
+        );
+        assert!(second.backing().as_str()[..10] < third.backing().as_str()[..10]);
+        assert_eq!(
+            crockford::decode(&first.backing().as_str()[..10]).unwrap(),
+            1_700_000_000_000u64.to_be_bytes()[2..]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no room for random characters")]
+    fn time_prefix_fills_seconds_id() {
+        TimePrefixedGen::<8>::new(TimestampResolution::Seconds);
+    }
+
+    #[test]
+    #[should_panic(expected = "no room for random characters")]
+    fn time_prefix_fills_millis_id() {
+        TimePrefixedGen::<10>::new(TimestampResolution::Millis);
+    }
+
+    #[test]
+    fn time_prefix_with_one_random_char() {
+        struct Tick;
+        impl IdDomain for Tick {
+            const NAME: &'static str = "Tick";
+            type Backing = TinyId<9>;
+            type Generator = TimePrefixedGen<9>;
+            type ConstRepr = ();
+        }
+
+        let generator = TimePrefixedGen::<9>::new(TimestampResolution::Seconds);
+        let id: Id<Tick> = generator.generate_at(SystemTime::now());
+        assert_eq!(id.backing().len(), 9);
+    }
+
+    #[test]
+    fn match_byte_patterns() {
+        let id = TinyId::<8>::from("PING");
+        assert!(id.matches_bytes(b"PING"));
+        assert!(!id.matches_bytes(b"PIN"));
+        assert!(!id.matches_bytes(b"PING\0"));
+        assert!(!id.matches_bytes(b"PONG"));
+        assert!(TinyId::<8>::from("").matches_bytes(&[]));
+        assert!(TinyId::<4>::from("FULL").matches_bytes(&[0x46, 0x55, 0x4c, 0x4c]));
+    }
+
+    #[test]
+    fn array_round_trip() {
+        let array = *b"abc\0\0\0\0\0";
+        let id = TinyId::from(array);
+        assert_eq!(id.as_str(), "abc");
+        assert_eq!(id, TinyId::<8>::from("abc"));
+        assert_eq!(id.into_array(), array);
+    }
+
+    #[test]
+    fn trimmed_space_padding() {
+        let id = TinyId::<8>::from("abc     ");
+        assert_eq!(id.as_str(), "abc     ");
+        assert_eq!(id.as_str_trimmed(), "abc");
+
+        let id = TinyId::<8>::from("abc \0\0\0\0");
+        assert_eq!(id.as_str_trimmed(), "abc");
+    }
+
+    #[test]
+    fn tuple_const_repr() {
+        use crate::StableTypeId;
+
+        struct Tool;
+        impl IdDomain for Tool {
+            const NAME: &'static str = "Tool";
+            type Backing = TinyId;
+            type Generator = ();
+            type ConstRepr = (&'static str, &'static str);
+        }
+
+        struct Saw;
+        impl StableTypeId<Tool> for Saw {
+            const STABLE_TYPE_ID: (&'static str, &'static str) = ("cutting", "saw");
+        }
+
+        assert_eq!(Saw::stable_type_id().backing().as_str(), "cutting:saw");
+    }
+
+    #[test]
+    fn resize_grow() {
+        let short = TinyId::<16>::from("sixteen-chars-id");
+        let long: TinyId<21> = short.resize().unwrap();
+        assert_eq!(long.as_str(), "sixteen-chars-id");
+        assert_eq!(long.len(), 16);
+    }
+
+    #[test]
+    fn resize_shrink() {
+        let long = TinyId::<21>::from("short");
+        let short: TinyId<8> = long.resize().unwrap();
+        assert_eq!(short.as_str(), "short");
+
+        let long = TinyId::<21>::from("this does not fit");
+        assert_eq!(
+            long.resize::<8>(),
+            Err(CapacityError {
+                capacity: 8,
+                len: 17
+            })
+        );
+    }
+
+    #[test]
+    fn macro_works() {
+        struct Bird;
+        tiny_id_domain!(Bird, "Bird");
+
+        let new_bird_id = Bird::generate_id();
+        assert_eq!(new_bird_id.backing().len(), 21);
+
+        const CONST_BIRD: &str = "constant";
+        let unconst_bird = Bird::new_id(CONST_BIRD);
+        assert_eq!(unconst_bird.backing().as_str(), "constant");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_fixed_size() {
+        fn round_trip<const N: usize>(text: &str) {
+            let id = TinyId::<N>::from(text);
+            let bytes = bincode::serialize(&id).unwrap();
+            assert_eq!(bytes.len(), N);
+
+            let read: TinyId<N> = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(read, id);
+            assert_eq!(read.as_str(), text);
+            assert_eq!(read.len(), text.len());
+        }
+
+        round_trip::<8>("abc");
+        round_trip::<8>("8 bytes!");
+        round_trip::<21>("V1StGXR8_Z5jdHi6B-myT");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_rejects_invalid_bytes() {
+        assert!(bincode::deserialize::<TinyId<4>>(&[0xff, 0, 0, 0]).is_err());
+        assert!(bincode::deserialize::<TinyId<4>>(&[b'a', 0, b'b', 0]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_is_string() {
+        let id = TinyId::<8>::from("abc");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""abc""#);
+        assert_eq!(serde_json::from_str::<TinyId<8>>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn bin_id_hex() {
+        let id = BinId::new([0x00, 0x01, 0xab, 0xff]);
+        assert_eq!(id.to_string(), "0001abff");
+        assert_eq!("0001abff".parse(), Ok(id));
+        assert_eq!("0001ab".parse::<BinId<4>>(), Err(ParseBinIdError));
+        assert_eq!("0001abzz".parse::<BinId<4>>(), Err(ParseBinIdError));
+        assert_eq!("+f+f".parse::<BinId<2>>(), Err(ParseBinIdError));
+    }
+
+    #[test]
+    fn bin_id_bytes_round_trip() {
+        let bytes = [0u8, 0, 7, 0];
+        let id = BinId::from(bytes);
+        assert_eq!(id.as_bytes(), &bytes);
+        assert_eq!(id.into_array(), bytes);
+        assert_ne!(id, BinId::new([0, 0, 7, 1]));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bin_id_serde() {
+        let id = BinId::new([0xde, 0xad, 0xbe, 0xef]);
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""deadbeef""#);
+        assert_eq!(serde_json::from_str::<BinId<4>>(&json).unwrap(), id);
+
+        let bytes = bincode::serialize(&id).unwrap();
+        assert_eq!(bincode::deserialize::<BinId<4>>(&bytes).unwrap(), id);
+    }
+
+    #[test]
+    fn macro_metadata() {
+        struct Bird;
+        tiny_id_domain!(Bird, "Bird", 12);
+
+        let meta = Bird::metadata();
+        assert_eq!(meta.name, "Bird");
+        assert_eq!(
+            meta.backing_type_name,
+            "stable_identifier::tiny_id::TinyId<12>"
+        );
+        assert!(meta.has_generator);
+        assert!(meta.has_const_repr);
+    }
+
+    #[test]
+    fn rng_gen_uses_provided_rng() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        struct Bird;
+        impl IdDomain for Bird {
+            const NAME: &'static str = "Bird";
+            type Backing = TinyId<12>;
+            type Generator = TinyIdRngGen<StdRng, 12>;
+            type ConstRepr = ();
+        }
+
+        let mut first = TinyIdRngGen::new(StdRng::seed_from_u64(42));
+        let mut second = TinyIdRngGen::new(StdRng::seed_from_u64(42));
+        let first_ids: Vec<_> = (0..10)
+            .map(|_| Bird::generate_id_stateful(&mut first))
+            .collect();
+        let second_ids: Vec<_> = (0..10)
+            .map(|_| Bird::generate_id_stateful(&mut second))
+            .collect();
+
+        assert_eq!(first_ids, second_ids);
+        assert!(first_ids.iter().all(|id| id.backing().len() == 12));
+        assert_ne!(first_ids[0], first_ids[1]);
+    }
+
+    #[test]
+    fn seeded_gen_streams_are_independent_per_domain() {
+        struct Cat;
+        impl IdDomain for Cat {
+            const NAME: &'static str = "Cat";
+            type Backing = TinyId<12>;
+            type Generator = SeededGen<12>;
+            type ConstRepr = ();
+        }
+
+        struct Dog;
+        impl IdDomain for Dog {
+            const NAME: &'static str = "Dog";
+            type Backing = TinyId<12>;
+            type Generator = SeededGen<12>;
+            type ConstRepr = ();
+        }
+
+        fn stream<D: IdDomain<Backing = TinyId<12>, Generator = SeededGen<12>>>(
+            master: u64,
+        ) -> Vec<String> {
+            let mut generator = SeededGen::<12>::for_domain::<D>(master);
+            (0..10)
+                .map(|_| D::generate_id_stateful(&mut generator).to_string())
+                .collect()
+        }
+
+        assert_eq!(stream::<Cat>(42), stream::<Cat>(42));
+        assert_ne!(stream::<Cat>(42), stream::<Cat>(43));
+
+        let cats = stream::<Cat>(42);
+        let dogs = stream::<Dog>(42);
+        assert!(cats.iter().all(|cat| !dogs.contains(cat)));
+        assert_ne!(domain_seed::<Cat>(42), domain_seed::<Dog>(42));
+
+        // Pinned, so that a change of the random stream is noticed rather than silently breaking fixtures.
+        assert_eq!(cats[0], "Cat [F1Ac0RxW9Uwi]");
+    }
+
+    fn generated_chars<A: Alphabet>() -> String {
+        struct Bird;
+        impl IdDomain for Bird {
+            const NAME: &'static str = "Bird";
+            type Backing = TinyId<64>;
+            type Generator = ();
+            type ConstRepr = ();
+        }
+
+        (0..10)
+            .map(|_| {
+                let id: Id<Bird> = TinyIdGen::<64, A>::generate_id();
+                id.backing().as_str().to_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn alphabet_url_safe() {
+        let chars = generated_chars::<UrlSafe>();
+        assert!(
+            chars
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        );
+    }
+
+    #[test]
+    fn alphabet_lowercase() {
+        let chars = generated_chars::<Lowercase>();
+        assert!(chars.chars().all(|c| c.is_ascii_lowercase()));
+    }
+
+    #[test]
+    fn alphabet_digits() {
+        let chars = generated_chars::<Digits>();
+        assert!(chars.chars().all(|c| c.is_ascii_digit()));
     }
 }
@@ -0,0 +1,180 @@
+use std::fmt::Debug;
+
+use crate::{
+    GenerateError, GenerateIdStateful, GenerateIdStateless, Id, IdDomain, TryGenerateIdStateful,
+};
+
+/// Generator that emits a `tracing` event at debug level for every identifier made by the generator `G`,
+/// with a `domain` field set to [`IdDomain::log_target`] and an `id` field holding the backing.
+///
+/// Useful for debugging where identifiers get allocated. Implements the same generator traits as `G`,
+/// so it can wrap any generator without changing how it is used:
+/// ```
+/// use stable_identifier::*;
+///
+/// define_id!(Ticket, "Ticket", backing = u32, generator = TracingGen<CounterGen<u32>> => TicketId);
+///
+/// let mut generator = TracingGen::new(CounterGen::default());
+/// let first: TicketId = Ticket::generate_id_stateful(&mut generator);
+/// assert_eq!(*first.backing(), 0);
+/// ```
+/// Failed generation through [`TryGenerateIdStateful`] is reported at debug level as well.
+///
+/// Only available with the `tracing` feature.
+pub struct TracingGen<G> {
+    inner: G,
+}
+
+impl<G> TracingGen<G> {
+    /// Wraps a (stateful) generator.
+    pub fn new(inner: G) -> Self {
+        Self { inner }
+    }
+
+    /// Returns the wrapped generator.
+    pub fn into_inner(self) -> G {
+        self.inner
+    }
+}
+
+fn traced<D>(id: Id<D>) -> Id<D>
+where
+    D: IdDomain,
+    D::Backing: Debug,
+{
+    tracing::debug!(domain = D::log_target(), id = ?id.backing(), "generated identifier");
+    id
+}
+
+impl<G, D> GenerateIdStateless<D> for TracingGen<G>
+where
+    D: IdDomain,
+    D::Backing: Debug,
+    G: GenerateIdStateless<D>,
+{
+    fn generate_id() -> Id<D> {
+        traced(G::generate_id())
+    }
+}
+
+impl<G, D> GenerateIdStateful<D> for TracingGen<G>
+where
+    D: IdDomain,
+    D::Backing: Debug,
+    G: GenerateIdStateful<D>,
+{
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        traced(self.inner.generate_id_stateful())
+    }
+}
+
+impl<G, D> TryGenerateIdStateful<D> for TracingGen<G>
+where
+    D: IdDomain,
+    D::Backing: Debug,
+    G: TryGenerateIdStateful<D>,
+{
+    fn try_generate_id_stateful(&mut self) -> Result<Id<D>, GenerateError> {
+        match self.inner.try_generate_id_stateful() {
+            Ok(id) => Ok(traced(id)),
+            Err(err) => {
+                tracing::debug!(domain = D::log_target(), error = %err, "failed to generate identifier");
+                Err(err)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{
+        Event, Metadata, Subscriber,
+        field::{Field, Visit},
+        span,
+    };
+
+    use super::*;
+    use crate::{CounterGen, OverflowPolicy};
+
+    /// Records the fields of every event as `name=value` strings.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Capture {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("{}={value:?}", field.name()));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    struct Ticket;
+    impl IdDomain for Ticket {
+        const NAME: &'static str = "Ticket";
+        type Backing = u8;
+        type Generator = TracingGen<CounterGen<u8>>;
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn emits_event_per_generated_id() {
+        let capture = Capture::default();
+        let mut generator = TracingGen::new(CounterGen::starting_at(254, OverflowPolicy::Error));
+
+        tracing::subscriber::with_default(capture.clone(), || {
+            assert_eq!(
+                Ticket::generate_id_stateful(&mut generator),
+                Ticket::new_id(254u8)
+            );
+            assert_eq!(
+                Ticket::try_generate_id_stateful(&mut generator),
+                Ok(Ticket::new_id(255u8))
+            );
+            assert_eq!(
+                Ticket::try_generate_id_stateful(&mut generator),
+                Err(GenerateError::Overflow)
+            );
+        });
+
+        let fields = capture.0.lock().unwrap();
+        assert_eq!(
+            *fields,
+            [
+                "message=generated identifier",
+                "domain=\"Ticket\"",
+                "id=254",
+                "message=generated identifier",
+                "domain=\"Ticket\"",
+                "id=255",
+                "message=failed to generate identifier",
+                "domain=\"Ticket\"",
+                "error=identifier generator overflowed",
+            ]
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Generation of [`Ulid`](https://docs.rs/ulid) identifiers, which are sortable by creation time.
+use std::time::SystemTime;
+
+use ulid::{Generator, Ulid};
+
+use crate::{
+    BoundedBacking, BoundedDisplay, GenerateError, GenerateIdStateful, Id, IdDomain,
+    TryGenerateIdStateful,
+};
+
+/// Generates [`Ulid`] identifiers that are guaranteed to be strictly increasing.
+///
+/// If the system clock goes backwards (e.g. after an NTP adjustment) or several identifiers are
+/// generated within the same millisecond, the random component of the previous identifier is
+/// incremented instead, as recommended by the ULID specification. This keeps identifiers ordered
+/// and unique, with the timestamp part staying at the latest time seen.
+///
+/// Generation only fails if the random component overflows, which is practically impossible.
+#[derive(Default)]
+pub struct UlidGen {
+    inner: Generator,
+}
+
+impl UlidGen {
+    /// Creates a new generator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates an identifier as if the current time was `time`.
+    pub fn generate_at<D>(&mut self, time: SystemTime) -> Result<Id<D>, GenerateError>
+    where
+        D: IdDomain<Backing = Ulid>,
+    {
+        self.inner
+            .generate_from_datetime(time)
+            .map(Id::new)
+            .map_err(|_| GenerateError::Overflow)
+    }
+}
+
+impl<D> TryGenerateIdStateful<D> for UlidGen
+where
+    D: IdDomain<Backing = Ulid>,
+{
+    fn try_generate_id_stateful(&mut self) -> Result<Id<D>, GenerateError> {
+        self.generate_at(SystemTime::now())
+    }
+}
+
+impl<D> GenerateIdStateful<D> for UlidGen
+where
+    D: IdDomain<Backing = Ulid>,
+{
+    /// Panics if the random component overflows.
+    fn generate_id_stateful(&mut self) -> Id<D> {
+        match self.generate_at(SystemTime::now()) {
+            Ok(id) => id,
+            Err(err) => panic!("{err} in domain {}", D::NAME),
+        }
+    }
+}
+
+impl BoundedBacking for Ulid {
+    const MIN: Self = Ulid(0);
+    const MAX: Self = Ulid(u128::MAX);
+}
+
+impl BoundedDisplay for Ulid {
+    const MAX_DISPLAY_LEN: usize = ulid::ULID_LEN;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    struct Event;
+    impl IdDomain for Event {
+        const NAME: &'static str = "Event";
+        type Backing = Ulid;
+        type Generator = UlidGen;
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn clock_regression_stays_monotonic() {
+        let now = SystemTime::now();
+        let mut generator = UlidGen::new();
+
+        let first: Id<Event> = generator.generate_at(now).unwrap();
+        let second: Id<Event> = generator.generate_at(now - Duration::from_secs(5)).unwrap();
+        let third: Id<Event> = generator
+            .generate_at(now - Duration::from_secs(10))
+            .unwrap();
+        let fourth: Id<Event> = generator.generate_at(now).unwrap();
+
+        assert!(first < second);
+        assert!(second < third);
+        assert!(third < fourth);
+        assert_eq!(
+            second.backing().timestamp_ms(),
+            first.backing().timestamp_ms()
+        );
+    }
+
+    #[test]
+    fn generates_increasing() {
+        let mut generator = UlidGen::new();
+        let ids: Vec<_> = (0..100)
+            .map(|_| Event::generate_id_stateful(&mut generator))
+            .collect();
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn generated_within_bounds() {
+        let mut generator = UlidGen::new();
+        let id = Event::generate_id_stateful(&mut generator);
+        assert!(Event::min_id() < id);
+        assert!(id < Event::max_id());
+    }
+}
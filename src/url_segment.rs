@@ -0,0 +1,91 @@
+use std::{fmt::Display, str::FromStr};
+
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+
+use crate::{Id, IdDomain, IdError};
+
+/// Everything except the unreserved characters of RFC 3986, which never need encoding.
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: Display,
+{
+    /// Renders the backing as a single URL path segment, percent-encoding any special characters.
+    ///
+    /// ```
+    /// use stable_identifier::*;
+    ///
+    /// struct Page;
+    /// impl IdDomain for Page {
+    ///     const NAME: &'static str = "Page";
+    ///     type Backing = String;
+    ///     type Generator = ();
+    ///     type ConstRepr = ();
+    /// }
+    ///
+    /// let id = Page::new_id("docs/intro");
+    /// assert_eq!(format!("/pages/{}", id.to_url_segment()), "/pages/docs%2Fintro");
+    /// ```
+    pub fn to_url_segment(&self) -> String {
+        utf8_percent_encode(&self.backing().to_string(), SEGMENT).to_string()
+    }
+}
+
+impl<D: IdDomain> Id<D>
+where
+    D::Backing: FromStr,
+    <D::Backing as FromStr>::Err: Display,
+{
+    /// Decodes a URL path segment made by [`Id::to_url_segment`], parses the backing and checks it with
+    /// [`IdDomain::validate`].
+    ///
+    /// Unlike [`IdDomain::try_new_id`], the decoded text is not normalized, so every identifier survives
+    /// the round trip through [`Id::to_url_segment`] unchanged.
+    pub fn from_url_segment(segment: &str) -> Result<Self, IdError> {
+        let decoded = percent_decode_str(segment)
+            .decode_utf8()
+            .map_err(|err| IdError::parse::<D>(segment, err))?;
+        let backing = decoded
+            .parse()
+            .map_err(|err| IdError::parse::<D>(segment, err))?;
+        D::validate(&backing)?;
+        Ok(Id::new(backing))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Page;
+    impl IdDomain for Page {
+        const NAME: &'static str = "Page";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn url_segment_round_trip() {
+        let id = Page::new_id("guides/getting started?");
+        let segment = id.to_url_segment();
+        assert_eq!(segment, "guides%2Fgetting%20started%3F");
+        assert_eq!(Id::<Page>::from_url_segment(&segment), Ok(id));
+
+        assert_eq!(Page::new_id("a-b_c.d~e").to_url_segment(), "a-b_c.d~e");
+        assert!(Id::<Page>::from_url_segment("%FF").is_err());
+    }
+
+    #[test]
+    fn url_segment_keeps_surrounding_whitespace() {
+        let id = Page::new_id(" a ");
+        let segment = id.to_url_segment();
+        assert_eq!(segment, "%20a%20");
+        assert_eq!(Id::<Page>::from_url_segment(&segment), Ok(id));
+    }
+}
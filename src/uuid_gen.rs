@@ -0,0 +1,91 @@
+//! Generation of [`Uuid`](https://docs.rs/uuid) identifiers.
+use std::marker::PhantomData;
+
+use uuid::Uuid;
+
+use crate::{BoundedDisplay, GenerateIdNamed, Id, IdDomain};
+
+/// Provides the namespace used by a [`NamespacedGen`].
+pub trait UuidNamespace {
+    /// The namespace that names are hashed together with.
+    const NAMESPACE: Uuid;
+}
+
+/// Deterministically generates UUIDv5 identifiers by hashing a name within the namespace of `NS`.
+///
+/// ```
+/// use stable_identifier::{*, uuid_gen::*};
+/// use uuid::Uuid;
+///
+/// struct Users;
+/// impl UuidNamespace for Users {
+///     const NAMESPACE: Uuid = Uuid::from_u128(0x9f3a5c1e_6b2d_4c8f_a1e7_3d5b8c2f4a61);
+/// }
+///
+/// struct User;
+/// impl IdDomain for User {
+///     const NAME: &'static str = "User";
+///     type Backing = Uuid;
+///     type Generator = NamespacedGen<Users>;
+///     type ConstRepr = ();
+/// }
+///
+/// assert_eq!(User::generate_named_id("alice"), User::generate_named_id("alice"));
+/// ```
+pub struct NamespacedGen<NS> {
+    _namespace: PhantomData<NS>,
+}
+
+impl<NS, D> GenerateIdNamed<D> for NamespacedGen<NS>
+where
+    NS: UuidNamespace,
+    D: IdDomain<Backing = Uuid>,
+{
+    fn generate_named_id(name: &[u8]) -> Id<D> {
+        Id::new(Uuid::new_v5(&NS::NAMESPACE, name))
+    }
+}
+
+impl BoundedDisplay for Uuid {
+    /// The hyphenated form, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`.
+    const MAX_DISPLAY_LEN: usize = uuid::fmt::Hyphenated::LENGTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dns;
+    impl UuidNamespace for Dns {
+        const NAMESPACE: Uuid = Uuid::NAMESPACE_DNS;
+    }
+
+    struct Host;
+    impl IdDomain for Host {
+        const NAME: &'static str = "Host";
+        type Backing = Uuid;
+        type Generator = NamespacedGen<Dns>;
+        type ConstRepr = ();
+    }
+
+    #[test]
+    fn display_bound() {
+        let id = Host::generate_named_id("example.com");
+        assert_eq!(Uuid::MAX_DISPLAY_LEN, 36);
+        assert_eq!(id.backing().to_string().len(), Uuid::MAX_DISPLAY_LEN);
+        assert_eq!(Id::<Host>::max_display_len(), "Host [".len() + 36 + 1);
+        assert_eq!(id.to_string().len(), Id::<Host>::max_display_len());
+    }
+
+    #[test]
+    fn same_name_same_id() {
+        let first = Host::generate_named_id("example.com");
+        let second = Host::generate_named_id(b"example.com");
+        assert_eq!(first, second);
+        assert_ne!(first, Host::generate_named_id("example.org"));
+        assert_eq!(
+            first.backing().to_string(),
+            "cfbff0d1-9375-5685-968c-48ce8b15ae17"
+        );
+    }
+}
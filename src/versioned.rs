@@ -0,0 +1,175 @@
+use std::{fmt::Display, hash::Hash};
+
+use crate::{Id, IdDomain};
+
+/// A domain whose identifiers carry a version, like `doc1@v2`, that doesn't affect which entity they identify.
+///
+/// Used by [`VersionedId`] to compare identifiers as entities.
+pub trait VersionedDomain: IdDomain {
+    /// The part of the backing that identifies the entity, i.e. without the version.
+    fn identity_key(backing: &Self::Backing) -> &str;
+}
+
+/// Opt-in wrapper comparing and hashing identifiers by their [`VersionedDomain::identity_key`].
+///
+/// Useful as a map key when any version of an entity should find the same entry. Display and
+/// serialization still use the full identifier:
+/// ```
+/// use stable_identifier::*;
+///
+/// struct Doc;
+/// impl IdDomain for Doc {
+///     const NAME: &'static str = "Doc";
+///     type Backing = String;
+///     type Generator = ();
+///     type ConstRepr = ();
+/// }
+/// impl VersionedDomain for Doc {
+///     fn identity_key(backing: &String) -> &str {
+///         backing.split_once('@').map_or(backing, |(key, _version)| key)
+///     }
+/// }
+///
+/// let v2 = VersionedId(Doc::new_id("doc1@v2"));
+/// let v3 = VersionedId(Doc::new_id("doc1@v3"));
+/// assert_eq!(v2, v3);
+/// assert_eq!(v2.to_string(), "Doc [doc1@v2]");
+/// ```
+pub struct VersionedId<D: VersionedDomain>(pub Id<D>);
+
+impl<D: VersionedDomain> VersionedId<D> {
+    /// The identity key of the wrapped identifier.
+    pub fn identity_key(&self) -> &str {
+        D::identity_key(self.0.backing())
+    }
+
+    /// Returns the wrapped identifier, including its version.
+    pub fn into_inner(self) -> Id<D> {
+        self.0
+    }
+}
+
+impl<D: VersionedDomain> From<Id<D>> for VersionedId<D> {
+    fn from(id: Id<D>) -> Self {
+        Self(id)
+    }
+}
+
+impl<D: VersionedDomain> PartialEq for VersionedId<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity_key() == other.identity_key()
+    }
+}
+
+impl<D: VersionedDomain> Eq for VersionedId<D> {}
+
+impl<D: VersionedDomain> Hash for VersionedId<D> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.identity_key().hash(state);
+    }
+}
+
+impl<D: VersionedDomain> Clone for VersionedId<D>
+where
+    D::Backing: Clone,
+{
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<D: VersionedDomain> std::fmt::Debug for VersionedId<D>
+where
+    D::Backing: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("VersionedId").field(&self.0).finish()
+    }
+}
+
+impl<D: VersionedDomain> Display for VersionedId<D>
+where
+    D::Backing: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    impl<D: VersionedDomain> Serialize for VersionedId<D>
+    where
+        D::Backing: Serialize,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.0.serialize(serializer)
+        }
+    }
+
+    impl<'de, D: VersionedDomain> Deserialize<'de> for VersionedId<D>
+    where
+        D::Backing: Deserialize<'de>,
+    {
+        fn deserialize<De>(deserializer: De) -> Result<Self, De::Error>
+        where
+            De: serde::Deserializer<'de>,
+        {
+            Id::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    struct Doc;
+    impl IdDomain for Doc {
+        const NAME: &'static str = "Doc";
+        type Backing = String;
+        type Generator = ();
+        type ConstRepr = ();
+    }
+    impl VersionedDomain for Doc {
+        fn identity_key(backing: &String) -> &str {
+            backing.split_once("@v").map_or(backing, |(key, _)| key)
+        }
+    }
+
+    #[test]
+    fn versions_are_same_entity() {
+        let v2 = VersionedId(Doc::new_id("doc1@v2"));
+        let v3 = VersionedId(Doc::new_id("doc1@v3"));
+        assert_eq!(v2, v3);
+        assert_ne!(v2, VersionedId(Doc::new_id("doc2@v2")));
+        assert_ne!(v2.0, v3.0);
+
+        let docs: HashSet<_> = [v2.clone(), v3].into_iter().collect();
+        assert_eq!(docs.len(), 1);
+        assert!(docs.contains(&VersionedId(Doc::new_id("doc1"))));
+
+        assert_eq!(v2.identity_key(), "doc1");
+        assert_eq!(v2.into_inner().backing(), "doc1@v2");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serializes_full_identifier() {
+        let v2 = VersionedId(Doc::new_id("doc1@v2"));
+        let json = serde_json::to_string(&v2).unwrap();
+        assert_eq!(json, serde_json::to_string(&v2.0).unwrap());
+
+        let read: VersionedId<Doc> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.0, v2.0);
+    }
+}